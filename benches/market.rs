@@ -0,0 +1,137 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use space_game_typings::fixed::npc_faction::NpcFaction;
+use space_game_typings::fixed::solarsystem::Solarsystem;
+use space_game_typings::market::{ItemMarket, Order, Side, Trade, Trader};
+
+// Markets are returned from the measured closures so dropping them is not measured.
+
+const SIZES: [u64; 3] = [100, 1_000, 10_000];
+
+/// The `ItemMarket` before it was based on price levels. Kept to compare against.
+#[derive(Default, Clone)]
+struct VecMarket {
+    buy: Vec<Order>,
+    sell: Vec<Order>,
+}
+
+impl VecMarket {
+    fn sort(&mut self) {
+        self.buy.sort_by_key(|o| o.date);
+        self.buy.sort_by_key(|o| std::cmp::Reverse(o.paperclips));
+
+        self.sell.sort_by_key(|o| o.date);
+        self.sell.sort_by_key(|o| o.paperclips);
+    }
+
+    fn cleanup(&mut self) {
+        self.buy = self.buy.iter().filter(|o| o.is_valid()).copied().collect();
+        self.sell = self.sell.iter().filter(|o| o.is_valid()).copied().collect();
+    }
+
+    fn resolve(&mut self) -> Vec<Trade> {
+        self.sort();
+        let mut trades = Vec::new();
+        for buy in &mut self.buy {
+            for sell in &mut self.sell {
+                if buy.amount == 0 || buy.paperclips < sell.paperclips {
+                    break;
+                }
+                if let Some((trade, remaining_buy, remaining_sell)) = Trade::resolve(*buy, *sell) {
+                    *buy = remaining_buy;
+                    *sell = remaining_sell;
+                    trades.push(trade);
+                }
+            }
+        }
+        self.cleanup();
+        trades
+    }
+}
+
+fn order(amount: u32, paperclips: u64) -> Order {
+    Order::new_now(
+        Solarsystem::default(),
+        0,
+        Trader::Npc(NpcFaction::Guards),
+        amount,
+        paperclips,
+    )
+}
+
+/// Buy orders below and sell orders above the price of `size` so nothing trades
+fn resting_orders(size: u64) -> (Vec<Order>, Vec<Order>) {
+    let buy = (0..size).map(|i| order(10, 1 + i)).collect();
+    let sell = (0..size).map(|i| order(10, size + 1 + i)).collect();
+    (buy, sell)
+}
+
+fn place_and_resolve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("place order into resting market");
+    for size in SIZES {
+        let (buy, sell) = resting_orders(size);
+        let incoming = order(25, size + 3);
+
+        group.bench_with_input(BenchmarkId::new("vec", size), &size, |b, _| {
+            b.iter_batched(
+                || VecMarket {
+                    buy: buy.clone(),
+                    sell: sell.clone(),
+                },
+                |mut market| {
+                    market.buy.push(incoming);
+                    let trades = market.resolve();
+                    (market, trades)
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("levels", size), &size, |b, _| {
+            b.iter_batched(
+                || ItemMarket::new(buy.clone(), sell.clone()),
+                |mut market| {
+                    market.insert(Side::Buy, incoming);
+                    let trades = market.resolve();
+                    (market, trades)
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn resolve_crossing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolve fully crossing market");
+    for size in SIZES {
+        let buy = (0..size).map(|i| order(10, size + i)).collect::<Vec<_>>();
+        let sell = (0..size).map(|i| order(10, 1 + i)).collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("vec", size), &size, |b, _| {
+            b.iter_batched(
+                || VecMarket {
+                    buy: buy.clone(),
+                    sell: sell.clone(),
+                },
+                |mut market| {
+                    let trades = market.resolve();
+                    (market, trades)
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("levels", size), &size, |b, _| {
+            b.iter_batched(
+                || ItemMarket::new(buy.clone(), sell.clone()),
+                |mut market| {
+                    let trades = market.resolve();
+                    (market, trades)
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, place_and_resolve, resolve_crossing);
+criterion_main!(benches);
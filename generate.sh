@@ -2,7 +2,7 @@
 set -e
 
 rm -rf bindings/ 2> /dev/null || true
-cargo test --tests --features=ts-rs
+cargo test --tests --features=ts-rs,schemars
 
 targetfile='static/typings.ts'
 cat typescript/manual.ts > $targetfile
@@ -1,4 +1,9 @@
 use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+
+use super::StaticsError;
 
 pub struct Database<K, V> {
     pub data: HashMap<K, V>,
@@ -27,10 +32,87 @@ where
         Self::parse_yaml(yaml_str).expect("failed to parse statics")
     }
 
+    /// Loads `<name>.yaml` (or `<name>.json`) from the base directory.
+    /// Every overlay directory containing a file with the same name patches the entries in order.
+    pub(super) fn load_from_dirs<P: AsRef<Path>>(
+        name: &str,
+        base: &Path,
+        overlays: &[P],
+    ) -> anyhow::Result<Self> {
+        let mut value = read_file(base, name)?
+            .with_context(|| format!("{} is missing in {}", name, base.display()))?;
+        for overlay in overlays {
+            if let Some(patch) = read_file(overlay.as_ref(), name)? {
+                merge(&mut value, patch);
+            }
+        }
+        let data = serde_yaml::from_value(value)
+            .with_context(|| format!("failed to parse statics {}", name))?;
+        Ok(Self { data })
+    }
+
+    /// Get the value of the given key
+    /// # Errors
+    /// Errors when the key is not part of the statics
+    pub fn try_get(&self, key: &K) -> Result<&V, StaticsError> {
+        self.data.get(key).ok_or_else(|| StaticsError::MissingKey {
+            database: std::any::type_name::<V>(),
+            key: format!("{:?}", key),
+        })
+    }
+
+    /// Get the value of the given key
+    /// # Panics
+    /// Panics when the key is not part of the statics. Use [`Self::try_get`] when this might happen.
     #[must_use]
     pub fn get(&self, key: &K) -> &V {
-        self.data.get(key).unwrap_or_else(|| {
-            unreachable!("statics has to contain every key. It is missing {:?}", key)
-        })
+        match self.try_get(key) {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
     }
 }
+
+/// Reads `<name>.yaml` or `<name>.json` from the directory.
+/// As json is valid yaml both are parsed by the yaml parser.
+fn read_file(dir: &Path, name: &str) -> anyhow::Result<Option<serde_yaml::Value>> {
+    for extension in ["yaml", "json"] {
+        let path = dir.join(format!("{}.{}", name, extension));
+        if path.is_file() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let value = serde_yaml::from_str(&content)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+/// Patches `base` with `patch`.
+/// Mappings are merged key by key, everything else is replaced.
+fn merge(base: &mut serde_yaml::Value, patch: serde_yaml::Value) {
+    match (base, patch) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+#[test]
+fn merge_patches_nested() {
+    let mut base: serde_yaml::Value = serde_yaml::from_str("a: {b: 1, c: [1, 2]}\nd: 4").unwrap();
+    let patch = serde_yaml::from_str("a: {c: [3]}\ne: 5").unwrap();
+    merge(&mut base, patch);
+    let expected: serde_yaml::Value =
+        serde_yaml::from_str("a: {b: 1, c: [3]}\nd: 4\ne: 5").unwrap();
+    assert_eq!(base, expected);
+}
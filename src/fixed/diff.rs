@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::serde_helper::present;
+
+use super::database::Database;
+use super::{facility, item, localization, module, region, shiplayout, solarsystem, Statics};
+
+/// Differences between two versions of the statics.
+/// Helpful to generate patch notes.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticsDiff {
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub constellations: DatabaseDiff<region::Constellation>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub facilities: DatabaseDiff<facility::Facility>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub items: DatabaseDiff<item::Item>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub localizations: DatabaseDiff<localization::Localizable>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub modules_passive: DatabaseDiff<module::Passive>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub modules_targeted: DatabaseDiff<module::Targeted>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub modules_untargeted: DatabaseDiff<module::Untargeted>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub regions: DatabaseDiff<region::Region>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub ship_layouts: DatabaseDiff<shiplayout::ShipLayout>,
+    #[serde(default, skip_serializing_if = "DatabaseDiff::is_empty")]
+    pub solarsystems: DatabaseDiff<solarsystem::Solarsystem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(
+    rename_all = "camelCase",
+    bound(deserialize = "K: Deserialize<'de> + Ord")
+)]
+pub struct DatabaseDiff<K: Ord> {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<K>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<K>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub changed: BTreeMap<K, Vec<FieldChange>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// Path to the field separated by dots like `stargates.Arama`
+    pub field: String,
+    /// Not existing before this change.
+    /// A field which existed with `null` is `Some(Value::Null)`.
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub before: Option<Value>,
+    /// Not existing anymore after this change
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub after: Option<Value>,
+}
+
+impl<K: Ord> Default for DatabaseDiff<K> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord> DatabaseDiff<K> {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl StaticsDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.constellations.is_empty()
+            && self.facilities.is_empty()
+            && self.items.is_empty()
+            && self.localizations.is_empty()
+            && self.modules_passive.is_empty()
+            && self.modules_targeted.is_empty()
+            && self.modules_untargeted.is_empty()
+            && self.regions.is_empty()
+            && self.ship_layouts.is_empty()
+            && self.solarsystems.is_empty()
+    }
+}
+
+impl Statics {
+    /// Compares these statics with a newer version of them.
+    /// # Errors
+    /// Errors when a value couldnt be serialized for comparison
+    pub fn diff(&self, newer: &Self) -> serde_json::Result<StaticsDiff> {
+        Ok(StaticsDiff {
+            constellations: diff_database(&self.constellations, &newer.constellations)?,
+            facilities: diff_database(&self.facilities, &newer.facilities)?,
+            items: diff_database(&self.items, &newer.items)?,
+            localizations: diff_database(&self.localizations, &newer.localizations)?,
+            modules_passive: diff_database(&self.modules_passive, &newer.modules_passive)?,
+            modules_targeted: diff_database(&self.modules_targeted, &newer.modules_targeted)?,
+            modules_untargeted: diff_database(&self.modules_untargeted, &newer.modules_untargeted)?,
+            regions: diff_database(&self.regions, &newer.regions)?,
+            ship_layouts: diff_database(&self.ship_layouts, &newer.ship_layouts)?,
+            solarsystems: diff_database(&self.solarsystems, &newer.solarsystems)?,
+        })
+    }
+}
+
+fn diff_database<K, V>(
+    older: &Database<K, V>,
+    newer: &Database<K, V>,
+) -> serde_json::Result<DatabaseDiff<K>>
+where
+    K: Copy + Ord + std::hash::Hash,
+    V: Serialize,
+{
+    let mut result = DatabaseDiff::default();
+    for key in older.data.keys() {
+        if !newer.data.contains_key(key) {
+            result.removed.push(*key);
+        }
+    }
+    for (key, value) in &newer.data {
+        match older.data.get(key) {
+            None => result.added.push(*key),
+            Some(before) => {
+                let mut changes = Vec::new();
+                diff_value(
+                    "",
+                    serde_json::to_value(before)?,
+                    serde_json::to_value(value)?,
+                    &mut changes,
+                );
+                if !changes.is_empty() {
+                    result.changed.insert(*key, changes);
+                }
+            }
+        }
+    }
+    result.added.sort();
+    result.removed.sort();
+    Ok(result)
+}
+
+fn diff_value(path: &str, before: Value, after: Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(mut after)) => {
+            let before = before.into_iter().collect::<BTreeMap<_, _>>();
+            for (key, before) in before {
+                let field = join(path, &key);
+                match after.remove(&key) {
+                    Some(after) => diff_value(&field, before, after, changes),
+                    None => changes.push(FieldChange {
+                        field,
+                        before: Some(before),
+                        after: None,
+                    }),
+                }
+            }
+            let added = after.into_iter().collect::<BTreeMap<_, _>>();
+            for (key, after) in added {
+                changes.push(FieldChange {
+                    field: join(path, &key),
+                    before: None,
+                    after: Some(after),
+                });
+            }
+        }
+        (before, after) => {
+            if before != after {
+                changes.push(FieldChange {
+                    field: path.to_string(),
+                    before: Some(before),
+                    after: Some(after),
+                });
+            }
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[test]
+fn same_statics_have_no_diff() {
+    let diff = Statics::default().diff(&Statics::default()).unwrap();
+    assert!(diff.is_empty());
+    assert_eq!(serde_json::to_string(&diff).unwrap(), "{}");
+}
+
+#[test]
+fn diff_finds_changed_fields() {
+    use shiplayout::ShipLayout;
+    use solarsystem::Solarsystem;
+    let older = Statics::default();
+    let mut newer = Statics::default();
+    newer
+        .ship_layouts
+        .data
+        .get_mut(&ShipLayout::Abis)
+        .unwrap()
+        .cargo_volume = 1337;
+    newer
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap()
+        .stargates
+        .insert(Solarsystem::Arama, 1);
+
+    let diff = older.diff(&newer).unwrap();
+    let abis = older.ship_layouts.get(&ShipLayout::Abis);
+    assert_eq!(
+        diff.ship_layouts.changed.get(&ShipLayout::Abis),
+        Some(&vec![FieldChange {
+            field: "cargoVolume".to_string(),
+            before: Some(abis.cargo_volume.into()),
+            after: Some(1337.into()),
+        }])
+    );
+    assert_eq!(
+        diff.solarsystems.changed.get(&Solarsystem::Vosu),
+        Some(&vec![FieldChange {
+            field: "stargates.Arama".to_string(),
+            before: None,
+            after: Some(1.into()),
+        }])
+    );
+    assert!(diff.items.is_empty());
+}
+
+#[test]
+fn diff_finds_added_and_removed() {
+    use item::{Item, Mineral};
+    let older = Statics::default();
+    let mut newer = Statics::default();
+    newer.items.data.remove(&Item::Mineral(Mineral::Derite));
+
+    let diff = older.diff(&newer).unwrap();
+    assert_eq!(diff.items.removed, vec![Item::Mineral(Mineral::Derite)]);
+    assert_eq!(diff.items.added, vec![]);
+
+    let diff = newer.diff(&older).unwrap();
+    assert_eq!(diff.items.added, vec![Item::Mineral(Mineral::Derite)]);
+}
+
+#[test]
+fn can_serde_parse_diff() {
+    use shiplayout::ShipLayout;
+    let older = Statics::default();
+    let mut newer = Statics::default();
+    newer
+        .ship_layouts
+        .data
+        .get_mut(&ShipLayout::Abis)
+        .unwrap()
+        .cpu = 1;
+    newer.ship_layouts.data.remove(&ShipLayout::Hecate);
+    let diff = older.diff(&newer).unwrap();
+    let json = serde_json::to_string(&diff).unwrap();
+    let parsed = serde_json::from_str::<StaticsDiff>(&json).unwrap();
+    assert_eq!(parsed, diff);
+    let yaml = serde_yaml::to_string(&diff).unwrap();
+    let parsed = serde_yaml::from_str::<StaticsDiff>(&yaml).unwrap();
+    assert_eq!(parsed, diff);
+}
+
+#[test]
+fn can_serde_parse_null_change() {
+    let changes = vec![
+        FieldChange {
+            field: "a".to_string(),
+            before: Some(Value::Null),
+            after: Some(1.into()),
+        },
+        FieldChange {
+            field: "b".to_string(),
+            before: None,
+            after: Some(Value::Null),
+        },
+    ];
+    let json = serde_json::to_string(&changes).unwrap();
+    let parsed = serde_json::from_str::<Vec<FieldChange>>(&json).unwrap();
+    assert_eq!(parsed, changes);
+    let yaml = serde_yaml::to_string(&changes).unwrap();
+    let parsed = serde_yaml::from_str::<Vec<FieldChange>>(&yaml).unwrap();
+    assert_eq!(parsed, changes);
+}
@@ -14,10 +14,10 @@ where
     let ordered = value.iter().collect::<BTreeMap<_, _>>();
 
     let json_str = serde_json::to_string_pretty(&ordered)?;
-    write_different(&format!("static/{}.json", filename), &json_str)?;
+    write_different(format!("static/{}.json", filename), &json_str)?;
 
     let yaml_str = serde_yaml::to_string(&ordered)?;
-    write_different(&format!("static/{}.yaml", filename), &yaml_str)?;
+    write_different(format!("static/{}.yaml", filename), &yaml_str)?;
 
     Ok(())
 }
@@ -31,19 +31,15 @@ fn write_different<P: AsRef<Path>>(path: P, contents: &str) -> std::io::Result<(
     Ok(())
 }
 
-fn graphviz(format: &str, filename: &str) -> anyhow::Result<()> {
-    let output = std::process::Command::new("dot")
-        .arg(format!("-T{}", format))
-        .arg(format!("{}.graphviz", filename))
-        .current_dir("static")
-        .output()?;
+#[test]
+fn check_statics() {
+    let findings = Statics::default().validate();
+    assert!(findings.is_empty(), "{:#?}", findings);
+}
 
-    if output.status.success() {
-        fs::write(format!("static/{}.{}", filename, format), output.stdout)?;
-        Ok(())
-    } else {
-        Err(anyhow::anyhow!("failed?"))
-    }
+#[test]
+fn check_constellation() -> anyhow::Result<()> {
+    export("constellation", &Statics::default().constellations.data)
 }
 
 #[test]
@@ -92,6 +88,16 @@ fn check_item() -> anyhow::Result<()> {
     export("item", &items.data)
 }
 
+#[test]
+fn check_localization() -> anyhow::Result<()> {
+    export("localization", &Statics::default().localizations.data)
+}
+
+#[test]
+fn check_region() -> anyhow::Result<()> {
+    export("region", &Statics::default().regions.data)
+}
+
 #[test]
 fn check_module_passive() -> anyhow::Result<()> {
     let all = Statics::default().modules_passive;
@@ -178,28 +184,16 @@ fn check_solarsystem() -> anyhow::Result<()> {
 }
 
 #[test]
-fn graphviz_solarsystems() -> anyhow::Result<()> {
-    let mut text = String::new();
-    text += "digraph {\n";
-
-    let all = Statics::default().solarsystems;
-
-    let mut ordered = all.data.iter().collect::<Vec<_>>();
-    ordered.sort_by(|a, b| b.1.security.cmp(&a.1.security));
-
-    for (key, system) in ordered {
-        text += "\t";
-        text += &format!(r#"{:?}[label="{} ({})"];"#, key, key, system.security);
-        text += "\n";
-
-        for target in system.stargates.keys() {
-            text += &format!("\t{:?} -> {:?};\n", key, target);
-        }
-    }
+fn analyze_solarsystems() -> anyhow::Result<()> {
+    let analysis = crate::universe::Universe::from(&Statics::default().solarsystems).analyze();
+    let json_str = serde_json::to_string_pretty(&analysis)?;
+    write_different("static/solarsystem-analysis.json", &json_str)?;
+    Ok(())
+}
 
-    text += "}\n";
-    fs::write("static/solarsystems.graphviz", &text)?;
-    drop(graphviz("svg", "solarsystems"));
-    drop(graphviz("png", "solarsystems"));
+#[test]
+fn render_solarsystems() -> anyhow::Result<()> {
+    let svg = crate::universe::map::render_svg(&Statics::default().solarsystems);
+    write_different("static/solarsystems.svg", &svg)?;
     Ok(())
 }
@@ -0,0 +1,30 @@
+use super::solarsystem::Solarsystem;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The statics do not contain the requested key.
+    /// This happens when code and data are not of the same version.
+    MissingKey { database: &'static str, key: String },
+    /// Stations are identified by an u8 so there can not be more than 256 in a solarsystem.
+    /// See `ValidationFinding::TooManyStations`.
+    TooManyStations(Solarsystem),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingKey { database, key } => {
+                write!(f, "Statics {} is missing the key {}.", database, key)
+            }
+            Self::TooManyStations(solarsystem) => {
+                write!(
+                    f,
+                    "Statics solarsystem {} has too many stations.",
+                    solarsystem
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
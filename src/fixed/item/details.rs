@@ -8,6 +8,7 @@ use super::Mineral;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ItemCategory")]
 pub enum Category {
     Mineral,
@@ -18,6 +19,7 @@ pub enum Category {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ItemDetails")]
 pub struct Details {
     /// This is mainly added for TypeScript to get more easily what kind of item this is.
@@ -28,5 +30,7 @@ pub struct Details {
     /// The amount of minerals returned when recycling one item
     #[serde(serialize_with = "ordered_map")]
     pub recycle: HashMap<Mineral, u32>,
-    // TODO: volume for storage in ships
+
+    /// Volume of one item when stored in ship cargo.
+    pub volume: u32,
 }
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Naming scheme: ends with 'ite' (derived from the Greek word 'lithos' just like human minerals).
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Mineral {
     Derite,
     Fylite,
@@ -14,6 +14,7 @@ pub use ore::Ore;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum Item {
     Mineral(Mineral),
@@ -64,11 +65,11 @@ impl FromStr for Item {
         serde_json::from_str(&format!(r#""{}""#, s))
     }
 }
-impl ToString for Item {
+impl std::fmt::Display for Item {
     /// Naively implemented via `serde_json`. Its a bit ugly but works for now.
-    fn to_string(&self) -> String {
-        let str = serde_json::to_string(self).unwrap();
-        str.trim_matches('"').to_string()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(str.trim_matches('"'))
     }
 }
 
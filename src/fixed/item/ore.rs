@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Naming scheme: ends with 'mit'.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Ore {
     Aromit,
     Solmit,
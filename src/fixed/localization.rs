@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::database::Database;
+use super::facility::Facility;
+use super::item::Item;
+use super::region::{Constellation, Region};
+use super::shiplayout::ShipLayout;
+use super::solarsystem::Solarsystem;
+
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Language {
+    /// The default language which is used when there is no text in the wanted language.
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+/// Everything within the statics which is shown to players.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum Localizable {
+    Facility(Facility),
+    Item(Item),
+    ShipLayout(ShipLayout),
+    Solarsystem(Solarsystem),
+    Constellation(Constellation),
+    Region(Region),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", rename = "LocalizationText")]
+pub struct Text {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+pub type Details = BTreeMap<Language, Text>;
+
+impl std::fmt::Display for Localizable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Facility(o) => write!(f, "{:?}", o),
+            Self::Item(o) => o.fmt(f),
+            Self::ShipLayout(o) => o.fmt(f),
+            Self::Solarsystem(o) => o.fmt(f),
+            Self::Constellation(o) => o.fmt(f),
+            Self::Region(o) => o.fmt(f),
+        }
+    }
+}
+
+impl From<Facility> for Localizable {
+    fn from(o: Facility) -> Self {
+        Self::Facility(o)
+    }
+}
+
+impl<T: Into<Item>> From<T> for Localizable {
+    fn from(o: T) -> Self {
+        Self::Item(o.into())
+    }
+}
+
+impl From<ShipLayout> for Localizable {
+    fn from(o: ShipLayout) -> Self {
+        Self::ShipLayout(o)
+    }
+}
+
+impl From<Solarsystem> for Localizable {
+    fn from(o: Solarsystem) -> Self {
+        Self::Solarsystem(o)
+    }
+}
+
+impl From<Constellation> for Localizable {
+    fn from(o: Constellation) -> Self {
+        Self::Constellation(o)
+    }
+}
+
+impl From<Region> for Localizable {
+    fn from(o: Region) -> Self {
+        Self::Region(o)
+    }
+}
+
+impl Database<Localizable, Details> {
+    /// Text in the given language.
+    /// Falls back to the default language when there is no text in the given language.
+    #[must_use]
+    pub fn text<L: Into<Localizable>>(&self, key: L, language: Language) -> Option<&Text> {
+        let details = self.data.get(&key.into())?;
+        details
+            .get(&language)
+            .or_else(|| details.get(&Language::default()))
+    }
+
+    /// Name in the given language.
+    /// Falls back to the default language and then to the name used in code.
+    #[must_use]
+    pub fn name<L: Into<Localizable>>(&self, key: L, language: Language) -> String {
+        let key = key.into();
+        self.text(key, language)
+            .map_or_else(|| key.to_string(), |text| text.name.clone())
+    }
+
+    /// Description in the given language.
+    /// Falls back to the default language.
+    #[must_use]
+    pub fn description<L: Into<Localizable>>(&self, key: L, language: Language) -> Option<&str> {
+        self.text(key, language)?.description.as_deref()
+    }
+}
+
+#[test]
+fn can_serde_parse_language() {
+    crate::test_helper::can_serde_parse(&Language::German);
+}
+
+#[test]
+fn can_serde_parse_localizable() {
+    use super::item::Ore;
+    crate::test_helper::can_serde_parse(&Localizable::Facility(Facility::Station));
+    crate::test_helper::can_serde_parse(&Localizable::Item(Ore::Aromit.into()));
+    crate::test_helper::can_serde_parse(&Localizable::ShipLayout(ShipLayout::Abis));
+    crate::test_helper::can_serde_parse(&Localizable::Solarsystem(Solarsystem::Vosu));
+    crate::test_helper::can_serde_parse(&Localizable::Constellation(Constellation::Abyss));
+    crate::test_helper::can_serde_parse(&Localizable::Region(Region::Heartland));
+}
+
+#[test]
+fn text_falls_back_to_default_language() {
+    let mut details = Details::new();
+    details.insert(
+        Language::English,
+        Text {
+            name: "Vosu".to_string(),
+            description: Some("Dead end".to_string()),
+        },
+    );
+    let mut db = Database {
+        data: std::collections::HashMap::new(),
+    };
+    db.data.insert(Solarsystem::Vosu.into(), details);
+
+    assert_eq!(db.name(Solarsystem::Vosu, Language::German), "Vosu");
+    assert_eq!(
+        db.description(Solarsystem::Vosu, Language::German),
+        Some("Dead end")
+    );
+    assert_eq!(db.name(Solarsystem::Arama, Language::German), "Arama");
+    assert_eq!(db.text(Solarsystem::Arama, Language::English), None);
+}
+
+#[test]
+fn statics_have_german_names() {
+    let statics = super::Statics::default();
+    assert_eq!(
+        statics
+            .localizations
+            .name(Facility::Station, Language::German),
+        "Station"
+    );
+    assert_eq!(
+        statics
+            .localizations
+            .name(super::item::Mineral::Derite, Language::English),
+        "Derite"
+    );
+}
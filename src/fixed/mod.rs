@@ -1,32 +1,49 @@
+use std::path::Path;
+
 use self::database::Database;
 
 pub mod facility;
 pub mod item;
+pub mod localization;
 pub mod module;
 pub mod npc_faction;
+pub mod region;
 pub mod round_effect;
 pub mod shiplayout;
 pub mod solarsystem;
 
 mod database;
+mod diff;
+mod error;
+mod validation;
+
+pub use diff::{DatabaseDiff, FieldChange, StaticsDiff};
+pub use error::Error as StaticsError;
+pub use validation::Finding as ValidationFinding;
 
 #[cfg(test)]
 mod do_data_export;
 
+pub type Constellations = Database<region::Constellation, region::ConstellationDetails>;
 pub type Facilites = Database<facility::Facility, facility::Details>;
 pub type Items = Database<item::Item, item::Details>;
+pub type Localizations = Database<localization::Localizable, localization::Details>;
 pub type ModulesPassive = Database<module::Passive, module::PassiveDetails>;
 pub type ModulesTargeted = Database<module::Targeted, module::TargetedDetails>;
 pub type ModulesUntargeted = Database<module::Untargeted, module::UntargetedDetails>;
+pub type Regions = Database<region::Region, region::RegionDetails>;
 pub type ShipLayouts = Database<shiplayout::ShipLayout, shiplayout::Details>;
 pub type Solarsystems = Database<solarsystem::Solarsystem, solarsystem::Details>;
 
 pub struct Statics {
+    pub constellations: Constellations,
     pub facilities: Facilites,
     pub items: Items,
+    pub localizations: Localizations,
     pub modules_passive: ModulesPassive,
     pub modules_targeted: ModulesTargeted,
     pub modules_untargeted: ModulesUntargeted,
+    pub regions: Regions,
     pub ship_layouts: ShipLayouts,
     pub solarsystems: Solarsystems,
 }
@@ -34,17 +51,92 @@ pub struct Statics {
 impl Default for Statics {
     fn default() -> Self {
         Self {
+            constellations: Database::p(include_str!("../../static/constellation.yaml")),
             facilities: Database::p(include_str!("../../static/facility.yaml")),
             items: Database::p(include_str!("../../static/item.yaml")),
+            localizations: Database::p(include_str!("../../static/localization.yaml")),
             modules_passive: Database::p(include_str!("../../static/module-passive.yaml")),
             modules_targeted: Database::p(include_str!("../../static/module-targeted.yaml")),
             modules_untargeted: Database::p(include_str!("../../static/module-untargeted.yaml")),
+            regions: Database::p(include_str!("../../static/region.yaml")),
             ship_layouts: Database::p(include_str!("../../static/ship-layout.yaml")),
             solarsystems: Database::p(include_str!("../../static/solarsystem.yaml")),
         }
     }
 }
 
+impl Statics {
+    /// Loads the statics at runtime from a directory structured like the `static` folder of this repo.
+    /// Every file can either be `.yaml` or `.json`.
+    /// # Errors
+    /// Errors when a file is missing or couldnt be parsed
+    pub fn load_from_dir<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
+        Self::load_from_dirs::<_, &Path>(dir, &[])
+    }
+
+    /// Loads the statics like [`Self::load_from_dir`] and patches them with the overlays afterwards.
+    ///
+    /// Overlays are applied in the given order and only need to contain the files and entries they change.
+    /// Maps are merged key by key so an overlay with `Abis: {cargoVolume: 30}` in `ship-layout.yaml` only changes the cargo of the Abis.
+    /// # Errors
+    /// Errors when a file is missing in the base directory or a file couldnt be parsed
+    pub fn load_from_dirs<B, O>(base: B, overlays: &[O]) -> anyhow::Result<Self>
+    where
+        B: AsRef<Path>,
+        O: AsRef<Path>,
+    {
+        let base = base.as_ref();
+        Ok(Self {
+            constellations: Database::load_from_dirs("constellation", base, overlays)?,
+            facilities: Database::load_from_dirs("facility", base, overlays)?,
+            items: Database::load_from_dirs("item", base, overlays)?,
+            localizations: Database::load_from_dirs("localization", base, overlays)?,
+            modules_passive: Database::load_from_dirs("module-passive", base, overlays)?,
+            modules_targeted: Database::load_from_dirs("module-targeted", base, overlays)?,
+            modules_untargeted: Database::load_from_dirs("module-untargeted", base, overlays)?,
+            regions: Database::load_from_dirs("region", base, overlays)?,
+            ship_layouts: Database::load_from_dirs("ship-layout", base, overlays)?,
+            solarsystems: Database::load_from_dirs("solarsystem", base, overlays)?,
+        })
+    }
+}
+
+#[test]
+fn can_load_from_dir() {
+    let statics = Statics::load_from_dir("static").unwrap();
+    let default = Statics::default();
+    assert_eq!(statics.items.data.len(), default.items.data.len());
+    assert_eq!(
+        statics.solarsystems.data.len(),
+        default.solarsystems.data.len()
+    );
+}
+
+#[test]
+fn overlay_patches_single_entry() {
+    use shiplayout::ShipLayout;
+    let overlay = crate::test_helper::unique_temp_dir("overlay-test");
+    std::fs::write(
+        overlay.join("ship-layout.yaml"),
+        "Abis:
+  cargoVolume: 1337
+",
+    )
+    .unwrap();
+
+    let statics = Statics::load_from_dirs("static", &[&overlay]).unwrap();
+    std::fs::remove_dir_all(&overlay).unwrap();
+    let default = Statics::default();
+    let patched = statics.ship_layouts.get(&ShipLayout::Abis);
+    let original = default.ship_layouts.get(&ShipLayout::Abis);
+    assert_eq!(patched.cargo_volume, 1337);
+    assert_eq!(patched.cpu, original.cpu);
+    assert_eq!(
+        statics.ship_layouts.get(&ShipLayout::Hecate).cargo_volume,
+        default.ship_layouts.get(&ShipLayout::Hecate).cargo_volume
+    );
+}
+
 #[test]
 fn can_generate_default_statics() {
     Statics::default();
@@ -4,6 +4,7 @@ use crate::serde_helper::is_default;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ModulePassive")]
 pub enum Passive {
     RookieArmorPlate,
@@ -11,6 +12,7 @@ pub enum Passive {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ModulePassiveDetails")]
 pub struct Details {
     pub required_cpu: u16,
@@ -5,6 +5,7 @@ use crate::serde_helper::ordered_vec;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ModuleTargeted")]
 pub enum Targeted {
     /// Weapon only meant to be used by the NpcFaction Guardians
@@ -16,6 +17,7 @@ pub enum Targeted {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ModuleTargetedDetails")]
 pub struct Details {
     pub required_cpu: u16,
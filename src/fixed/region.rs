@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use crate::serde_helper::ordered_vec;
+
+use super::database::Database;
+use super::solarsystem::Solarsystem;
+use super::Statics;
+
+/// Group of solarsystems which are close to each other
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Constellation {
+    Cradle,
+    Crossing,
+    Abyss,
+}
+
+/// Group of constellations
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Region {
+    Heartland,
+    Borderlands,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ConstellationDetails {
+    #[serde(serialize_with = "ordered_vec")]
+    pub solarsystems: Vec<Solarsystem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RegionDetails {
+    #[serde(serialize_with = "ordered_vec")]
+    pub constellations: Vec<Constellation>,
+}
+
+impl std::fmt::Display for Constellation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Database<Constellation, ConstellationDetails> {
+    /// The constellation the solarsystem is part of
+    #[must_use]
+    pub fn of_solarsystem(&self, solarsystem: Solarsystem) -> Option<Constellation> {
+        let mut all = self
+            .data
+            .iter()
+            .filter(|(_, details)| details.solarsystems.contains(&solarsystem))
+            .map(|(constellation, _)| *constellation)
+            .collect::<Vec<_>>();
+        // Validation ensures there is only one. Sort for a stable result anyway.
+        all.sort();
+        all.first().copied()
+    }
+}
+
+impl Database<Region, RegionDetails> {
+    /// The region the constellation is part of
+    #[must_use]
+    pub fn of_constellation(&self, constellation: Constellation) -> Option<Region> {
+        let mut all = self
+            .data
+            .iter()
+            .filter(|(_, details)| details.constellations.contains(&constellation))
+            .map(|(region, _)| *region)
+            .collect::<Vec<_>>();
+        all.sort();
+        all.first().copied()
+    }
+}
+
+impl Statics {
+    /// The region the solarsystem is part of
+    #[must_use]
+    pub fn region_of(&self, solarsystem: Solarsystem) -> Option<Region> {
+        let constellation = self.constellations.of_solarsystem(solarsystem)?;
+        self.regions.of_constellation(constellation)
+    }
+
+    /// All the solarsystems within the constellations of the region
+    #[must_use]
+    pub fn solarsystems_in_region(&self, region: Region) -> Vec<Solarsystem> {
+        let mut result = self
+            .regions
+            .data
+            .get(&region)
+            .map(|details| {
+                details
+                    .constellations
+                    .iter()
+                    .filter_map(|o| self.constellations.data.get(o))
+                    .flat_map(|o| o.solarsystems.iter().copied())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        result.sort();
+        result
+    }
+}
+
+#[test]
+fn can_serde_parse_constellation() {
+    crate::test_helper::can_serde_parse(&Constellation::Cradle);
+}
+
+#[test]
+fn can_serde_parse_region() {
+    crate::test_helper::can_serde_parse(&Region::Borderlands);
+}
+
+#[test]
+fn lookup_solarsystem_to_region() {
+    let statics = Statics::default();
+    assert_eq!(
+        statics.constellations.of_solarsystem(Solarsystem::Vosu),
+        Some(Constellation::Abyss)
+    );
+    assert_eq!(
+        statics.regions.of_constellation(Constellation::Abyss),
+        Some(Region::Borderlands)
+    );
+    assert_eq!(
+        statics.region_of(Solarsystem::Wabinihwa),
+        Some(Region::Heartland)
+    );
+}
+
+#[test]
+fn lookup_region_to_solarsystems() {
+    let statics = Statics::default();
+    assert_eq!(
+        statics.solarsystems_in_region(Region::Borderlands),
+        [Solarsystem::Arama, Solarsystem::Plagar, Solarsystem::Vosu]
+    );
+    assert_eq!(
+        statics
+            .constellations
+            .get(&Constellation::Cradle)
+            .solarsystems,
+        [
+            Solarsystem::Wabinihwa,
+            Solarsystem::Iramil,
+            Solarsystem::Liagi
+        ]
+    );
+}
+
+#[test]
+fn every_solarsystem_has_a_region() {
+    let statics = Statics::default();
+    for solarsystem in statics.solarsystems.data.keys() {
+        assert!(statics.region_of(*solarsystem).is_some(), "{}", solarsystem);
+    }
+}
@@ -7,6 +7,7 @@ use super::round_effect::RoundEffect;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum ShipClass {
     RookieShip,
@@ -15,13 +16,17 @@ pub enum ShipClass {
     Battleship,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ShipLayout {
     /// Special Ship used by the Guardians to secure the High Sec
     Paladin,
 
     /// Beginner default ship
+    #[default]
     Abis,
 
     Hecate,
@@ -29,6 +34,7 @@ pub enum ShipLayout {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ShipLayoutDetails")]
 pub struct Details {
     pub class: ShipClass,
@@ -40,9 +46,9 @@ pub struct Details {
     pub slots_untargeted: u8,
     pub slots_passive: u8,
 
-    /// Total slots that can be used for stored goods.
-    /// Will probably be replaced in the future with a `cargo_volume` or something like that.
-    pub cargo_slots: u32,
+    /// Total volume that can be used for stored goods.
+    /// See the `volume` of the item details.
+    pub cargo_volume: u32,
 
     #[serde(flatten)]
     pub collateral: Collateral,
@@ -56,9 +62,3 @@ impl std::fmt::Display for ShipLayout {
         write!(f, "{:?}", self)
     }
 }
-
-impl Default for ShipLayout {
-    fn default() -> Self {
-        Self::Abis
-    }
-}
@@ -5,10 +5,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::serde_helper::ordered_map;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Solarsystem {
     /// The home system for new players
+    #[default]
     Wabinihwa,
 
     Arama,
@@ -24,12 +28,6 @@ impl std::fmt::Display for Solarsystem {
     }
 }
 
-impl Default for Solarsystem {
-    fn default() -> Self {
-        Self::Wabinihwa
-    }
-}
-
 impl FromStr for Solarsystem {
     type Err = serde_json::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -39,6 +37,7 @@ impl FromStr for Solarsystem {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SolarsystemDetails")]
 pub struct Details {
     /// Percentage
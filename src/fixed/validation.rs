@@ -0,0 +1,683 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use serde::Serialize;
+
+use super::item::{Category, Item, Mineral};
+use super::localization::{Language, Localizable};
+use super::module::Module;
+use super::region::{Constellation, Region};
+use super::shiplayout::ShipLayout;
+use super::solarsystem::Solarsystem;
+use super::Statics;
+
+/// Something inconsistent within the statics.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "details")]
+pub enum Finding {
+    EmptyDatabase(&'static str),
+
+    /// Every module needs an `Item` entry with the category module
+    ModuleWithoutItem(Module),
+    ItemWrongCategory {
+        item: Item,
+        expected: Category,
+        actual: Category,
+    },
+    /// Minerals are the base components and can not be recycled
+    MineralWithRecycle(Mineral),
+    /// Everything that isnt a mineral has to be recyclable
+    ItemWithoutRecycle(Item),
+    /// Every item needs some volume in order to limit the cargo
+    ItemWithoutVolume(Item),
+    /// The recycled mineral has no `Item` entry
+    UnknownRecycleMineral {
+        item: Item,
+        mineral: Mineral,
+    },
+
+    /// Modules need at least some cpu or powergrid
+    ModuleWithoutRequirements(Module),
+    ModuleWithoutEffects(Module),
+
+    ShipLayoutWithoutCapacitor(ShipLayout),
+    ShipLayoutNotAlive(ShipLayout),
+
+    SecurityAbove100(Solarsystem),
+    StargatePlanetOutOfRange {
+        solarsystem: Solarsystem,
+        target: Solarsystem,
+        planet: u8,
+    },
+    StationPlanetOutOfRange {
+        solarsystem: Solarsystem,
+        station: u8,
+        planet: u8,
+    },
+    /// Stations are identified by an u8 so at most 256 fit into a solarsystem
+    TooManyStations(Solarsystem),
+    StargateTargetUnknown {
+        solarsystem: Solarsystem,
+        target: Solarsystem,
+    },
+    /// The target system has no stargate back
+    StargateNotSymmetric {
+        solarsystem: Solarsystem,
+        target: Solarsystem,
+    },
+    /// Can not be reached via stargates from the home system (`Solarsystem::default()`)
+    SolarsystemUnreachable(Solarsystem),
+
+    /// Every solarsystem has to be part of exactly one constellation
+    SolarsystemWithoutConstellation(Solarsystem),
+    SolarsystemInMultipleConstellations(Solarsystem),
+    ConstellationUnknownSolarsystem {
+        constellation: Constellation,
+        solarsystem: Solarsystem,
+    },
+    /// Every constellation has to be part of exactly one region
+    ConstellationWithoutRegion(Constellation),
+    ConstellationInMultipleRegions(Constellation),
+    RegionUnknownConstellation {
+        region: Region,
+        constellation: Constellation,
+    },
+
+    /// Has no text in the default language
+    MissingLocalization(Localizable),
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyDatabase(database) => write!(f, "{} is empty", database),
+            Self::ModuleWithoutItem(module) => {
+                write!(f, "module {:?} has no item entry", module)
+            }
+            Self::ItemWrongCategory {
+                item,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "item {} should have category {:?} but has {:?}",
+                item, expected, actual
+            ),
+            Self::MineralWithRecycle(mineral) => {
+                write!(f, "mineral {} can be recycled", mineral)
+            }
+            Self::ItemWithoutRecycle(item) => write!(f, "item {} can not be recycled", item),
+            Self::ItemWithoutVolume(item) => write!(f, "item {} has no volume", item),
+            Self::UnknownRecycleMineral { item, mineral } => write!(
+                f,
+                "item {} recycles into {} which has no item entry",
+                item, mineral
+            ),
+            Self::ModuleWithoutRequirements(module) => {
+                write!(f, "module {:?} requires neither cpu nor powergrid", module)
+            }
+            Self::ModuleWithoutEffects(module) => write!(f, "module {:?} has no effects", module),
+            Self::ShipLayoutWithoutCapacitor(layout) => {
+                write!(f, "ship layout {} has no capacitor", layout)
+            }
+            Self::ShipLayoutNotAlive(layout) => write!(f, "ship layout {} is not alive", layout),
+            Self::SecurityAbove100(_)
+            | Self::StargatePlanetOutOfRange { .. }
+            | Self::StationPlanetOutOfRange { .. }
+            | Self::TooManyStations(_)
+            | Self::StargateTargetUnknown { .. }
+            | Self::StargateNotSymmetric { .. }
+            | Self::SolarsystemUnreachable(_)
+            | Self::SolarsystemWithoutConstellation(_)
+            | Self::SolarsystemInMultipleConstellations(_)
+            | Self::ConstellationUnknownSolarsystem { .. }
+            | Self::ConstellationWithoutRegion(_)
+            | Self::ConstellationInMultipleRegions(_)
+            | Self::RegionUnknownConstellation { .. } => self.fmt_universe(f),
+            Self::MissingLocalization(key) => write!(
+                f,
+                "{} has no localization in the default language {:?}",
+                key,
+                Language::default()
+            ),
+        }
+    }
+}
+
+impl Finding {
+    /// Messages of the solarsystem, constellation and region findings
+    fn fmt_universe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SecurityAbove100(solarsystem) => {
+                write!(f, "solarsystem {} has a security above 100", solarsystem)
+            }
+            Self::StargatePlanetOutOfRange {
+                solarsystem,
+                target,
+                planet,
+            } => write!(
+                f,
+                "stargate {} -> {} is at planet {} which does not exist",
+                solarsystem, target, planet
+            ),
+            Self::StationPlanetOutOfRange {
+                solarsystem,
+                station,
+                planet,
+            } => write!(
+                f,
+                "station {} in {} is at planet {} which does not exist",
+                station, solarsystem, planet
+            ),
+            Self::TooManyStations(solarsystem) => write!(
+                f,
+                "solarsystem {} has more stations than can be identified",
+                solarsystem
+            ),
+            Self::StargateTargetUnknown {
+                solarsystem,
+                target,
+            } => write!(
+                f,
+                "stargate {} -> {} targets an unknown solarsystem",
+                solarsystem, target
+            ),
+            Self::StargateNotSymmetric {
+                solarsystem,
+                target,
+            } => write!(
+                f,
+                "stargate {} -> {} has no stargate back",
+                solarsystem, target
+            ),
+            Self::SolarsystemUnreachable(solarsystem) => write!(
+                f,
+                "solarsystem {} can not be reached from {}",
+                solarsystem,
+                Solarsystem::default()
+            ),
+            Self::SolarsystemWithoutConstellation(solarsystem) => {
+                write!(f, "solarsystem {} is in no constellation", solarsystem)
+            }
+            Self::SolarsystemInMultipleConstellations(solarsystem) => write!(
+                f,
+                "solarsystem {} is in multiple constellations",
+                solarsystem
+            ),
+            Self::ConstellationUnknownSolarsystem {
+                constellation,
+                solarsystem,
+            } => write!(
+                f,
+                "constellation {} contains the unknown solarsystem {}",
+                constellation, solarsystem
+            ),
+            Self::ConstellationWithoutRegion(constellation) => {
+                write!(f, "constellation {} is in no region", constellation)
+            }
+            Self::ConstellationInMultipleRegions(constellation) => {
+                write!(f, "constellation {} is in multiple regions", constellation)
+            }
+            Self::RegionUnknownConstellation {
+                region,
+                constellation,
+            } => write!(
+                f,
+                "region {} contains the unknown constellation {}",
+                region, constellation
+            ),
+            _ => unreachable!("only called for universe findings"),
+        }
+    }
+}
+
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+    map.iter().collect()
+}
+
+impl Statics {
+    /// Checks the cross references and constraints between the statics.
+    /// Returns every finding, an empty result means the statics are consistent.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        self.validate_items(&mut findings);
+        self.validate_modules(&mut findings);
+        self.validate_ship_layouts(&mut findings);
+        self.validate_solarsystems(&mut findings);
+        self.validate_regions(&mut findings);
+        self.validate_localizations(&mut findings);
+        findings
+    }
+
+    fn validate_items(&self, findings: &mut Vec<Finding>) {
+        if self.facilities.data.is_empty() {
+            findings.push(Finding::EmptyDatabase("facility"));
+        }
+        if self.items.data.is_empty() {
+            findings.push(Finding::EmptyDatabase("item"));
+        }
+
+        for (item, details) in sorted(&self.items.data) {
+            let expected = match item {
+                Item::Mineral(_) => Category::Mineral,
+                Item::Module(_) => Category::Module,
+                Item::Ore(_) => Category::Ore,
+            };
+            if expected != details.category {
+                findings.push(Finding::ItemWrongCategory {
+                    item: *item,
+                    expected,
+                    actual: details.category,
+                });
+            }
+
+            if let Item::Mineral(mineral) = item {
+                if !details.recycle.is_empty() {
+                    findings.push(Finding::MineralWithRecycle(*mineral));
+                }
+            } else if details.recycle.is_empty() {
+                findings.push(Finding::ItemWithoutRecycle(*item));
+            }
+
+            if details.volume == 0 {
+                findings.push(Finding::ItemWithoutVolume(*item));
+            }
+
+            for mineral in sorted(&details.recycle).keys() {
+                if !self.items.data.contains_key(&Item::Mineral(**mineral)) {
+                    findings.push(Finding::UnknownRecycleMineral {
+                        item: *item,
+                        mineral: **mineral,
+                    });
+                }
+            }
+        }
+    }
+
+    fn validate_modules(&self, findings: &mut Vec<Finding>) {
+        let mut modules = BTreeMap::new();
+        for (key, value) in &self.modules_passive.data {
+            let requires = value.required_cpu.saturating_add(value.required_powergrid);
+            // TODO: ensure some attribute does something
+            modules.insert(Module::Passive(*key), (requires, true));
+        }
+        for (key, value) in &self.modules_targeted.data {
+            let requires = value.required_cpu.saturating_add(value.required_powergrid);
+            let has_effects = !value.effects_origin.is_empty() || !value.effects_target.is_empty();
+            modules.insert(Module::Targeted(*key), (requires, has_effects));
+        }
+        for (key, value) in &self.modules_untargeted.data {
+            let requires = value.required_cpu.saturating_add(value.required_powergrid);
+            modules.insert(
+                Module::Untargeted(*key),
+                (requires, !value.effects.is_empty()),
+            );
+        }
+
+        for (database, is_empty) in [
+            ("module-passive", self.modules_passive.data.is_empty()),
+            ("module-targeted", self.modules_targeted.data.is_empty()),
+            ("module-untargeted", self.modules_untargeted.data.is_empty()),
+        ] {
+            if is_empty {
+                findings.push(Finding::EmptyDatabase(database));
+            }
+        }
+
+        for (module, (requires, has_effects)) in modules {
+            if !self.items.data.contains_key(&module.into()) {
+                findings.push(Finding::ModuleWithoutItem(module));
+            }
+            if requires == 0 {
+                findings.push(Finding::ModuleWithoutRequirements(module));
+            }
+            if !has_effects {
+                findings.push(Finding::ModuleWithoutEffects(module));
+            }
+        }
+    }
+
+    fn validate_ship_layouts(&self, findings: &mut Vec<Finding>) {
+        if self.ship_layouts.data.is_empty() {
+            findings.push(Finding::EmptyDatabase("ship-layout"));
+        }
+        for (layout, details) in sorted(&self.ship_layouts.data) {
+            if details.collateral.capacitor == 0 {
+                findings.push(Finding::ShipLayoutWithoutCapacitor(*layout));
+            }
+            if !details.collateral.is_alive() {
+                findings.push(Finding::ShipLayoutNotAlive(*layout));
+            }
+        }
+    }
+
+    fn validate_solarsystems(&self, findings: &mut Vec<Finding>) {
+        let all = &self.solarsystems.data;
+        if all.is_empty() {
+            findings.push(Finding::EmptyDatabase("solarsystem"));
+            return;
+        }
+
+        for (solarsystem, details) in sorted(all) {
+            if details.security > 100 {
+                findings.push(Finding::SecurityAbove100(*solarsystem));
+            }
+
+            for (target, planet) in sorted(&details.stargates) {
+                if planet > &details.planets {
+                    findings.push(Finding::StargatePlanetOutOfRange {
+                        solarsystem: *solarsystem,
+                        target: *target,
+                        planet: *planet,
+                    });
+                }
+                match all.get(target) {
+                    None => findings.push(Finding::StargateTargetUnknown {
+                        solarsystem: *solarsystem,
+                        target: *target,
+                    }),
+                    Some(target_details) => {
+                        if !target_details.stargates.contains_key(solarsystem) {
+                            findings.push(Finding::StargateNotSymmetric {
+                                solarsystem: *solarsystem,
+                                target: *target,
+                            });
+                        }
+                    }
+                }
+            }
+
+            if details.stations.len() > usize::from(u8::MAX) + 1 {
+                findings.push(Finding::TooManyStations(*solarsystem));
+            }
+            for (station, planet) in details.stations.iter().enumerate() {
+                if planet > &details.planets {
+                    findings.push(Finding::StationPlanetOutOfRange {
+                        solarsystem: *solarsystem,
+                        station: u8::try_from(station).unwrap_or(u8::MAX),
+                        planet: *planet,
+                    });
+                }
+            }
+        }
+
+        let home = Solarsystem::default();
+        let mut reachable = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        if all.contains_key(&home) {
+            reachable.insert(home);
+            queue.push_back(home);
+        }
+        while let Some(current) = queue.pop_front() {
+            if let Some(details) = all.get(&current) {
+                for target in details.stargates.keys() {
+                    if all.contains_key(target) && reachable.insert(*target) {
+                        queue.push_back(*target);
+                    }
+                }
+            }
+        }
+        for solarsystem in sorted(all).keys() {
+            if !reachable.contains(*solarsystem) {
+                findings.push(Finding::SolarsystemUnreachable(**solarsystem));
+            }
+        }
+    }
+
+    fn validate_regions(&self, findings: &mut Vec<Finding>) {
+        if self.constellations.data.is_empty() {
+            findings.push(Finding::EmptyDatabase("constellation"));
+        }
+        if self.regions.data.is_empty() {
+            findings.push(Finding::EmptyDatabase("region"));
+        }
+
+        let mut solarsystem_count = BTreeMap::new();
+        for (constellation, details) in sorted(&self.constellations.data) {
+            for solarsystem in &details.solarsystems {
+                if !self.solarsystems.data.contains_key(solarsystem) {
+                    findings.push(Finding::ConstellationUnknownSolarsystem {
+                        constellation: *constellation,
+                        solarsystem: *solarsystem,
+                    });
+                }
+                *solarsystem_count.entry(*solarsystem).or_insert(0) += 1;
+            }
+        }
+        for solarsystem in sorted(&self.solarsystems.data).keys() {
+            match solarsystem_count.get(*solarsystem) {
+                None => findings.push(Finding::SolarsystemWithoutConstellation(**solarsystem)),
+                Some(1) => {}
+                Some(_) => {
+                    findings.push(Finding::SolarsystemInMultipleConstellations(**solarsystem));
+                }
+            }
+        }
+
+        let mut constellation_count = BTreeMap::new();
+        for (region, details) in sorted(&self.regions.data) {
+            for constellation in &details.constellations {
+                if !self.constellations.data.contains_key(constellation) {
+                    findings.push(Finding::RegionUnknownConstellation {
+                        region: *region,
+                        constellation: *constellation,
+                    });
+                }
+                *constellation_count.entry(*constellation).or_insert(0) += 1;
+            }
+        }
+        for constellation in sorted(&self.constellations.data).keys() {
+            match constellation_count.get(*constellation) {
+                None => findings.push(Finding::ConstellationWithoutRegion(**constellation)),
+                Some(1) => {}
+                Some(_) => findings.push(Finding::ConstellationInMultipleRegions(**constellation)),
+            }
+        }
+    }
+
+    fn validate_localizations(&self, findings: &mut Vec<Finding>) {
+        let keys = self
+            .facilities
+            .data
+            .keys()
+            .map(|o| Localizable::from(*o))
+            .chain(self.items.data.keys().map(|o| Localizable::from(*o)))
+            .chain(self.ship_layouts.data.keys().map(|o| Localizable::from(*o)))
+            .chain(self.solarsystems.data.keys().map(|o| Localizable::from(*o)))
+            .chain(
+                self.constellations
+                    .data
+                    .keys()
+                    .map(|o| Localizable::from(*o)),
+            )
+            .chain(self.regions.data.keys().map(|o| Localizable::from(*o)))
+            .collect::<BTreeSet<_>>();
+        for key in keys {
+            let has_default = self
+                .localizations
+                .data
+                .get(&key)
+                .is_some_and(|o| o.contains_key(&Language::default()));
+            if !has_default {
+                findings.push(Finding::MissingLocalization(key));
+            }
+        }
+    }
+}
+
+#[test]
+fn default_statics_are_valid() {
+    let findings = Statics::default().validate();
+    assert_eq!(findings, vec![]);
+}
+
+#[test]
+fn finds_module_without_item() {
+    use super::module::Targeted;
+    let mut statics = Statics::default();
+    statics.items.data.remove(&Targeted::RookieLaser.into());
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::ModuleWithoutItem(Targeted::RookieLaser.into())]
+    );
+}
+
+#[test]
+fn finds_unknown_recycle_mineral() {
+    use super::module::Targeted;
+    let mut statics = Statics::default();
+    statics.items.data.remove(&Mineral::Derite.into());
+    let findings = statics.validate();
+    assert!(findings.contains(&Finding::UnknownRecycleMineral {
+        item: Targeted::RookieLaser.into(),
+        mineral: Mineral::Derite,
+    }));
+}
+
+#[test]
+fn finds_one_directional_stargate() {
+    let mut statics = Statics::default();
+    statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap()
+        .stargates
+        .clear();
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::StargateNotSymmetric {
+            solarsystem: Solarsystem::Plagar,
+            target: Solarsystem::Vosu,
+        }]
+    );
+}
+
+#[test]
+fn finds_unreachable_solarsystem() {
+    let mut statics = Statics::default();
+    for details in statics.solarsystems.data.values_mut() {
+        details.stargates.remove(&Solarsystem::Vosu);
+    }
+    statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap()
+        .stargates
+        .clear();
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::SolarsystemUnreachable(Solarsystem::Vosu)]
+    );
+}
+
+#[test]
+fn finds_missing_localization() {
+    let mut statics = Statics::default();
+    statics.localizations.data.remove(&Solarsystem::Vosu.into());
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::MissingLocalization(Solarsystem::Vosu.into())]
+    );
+}
+
+#[test]
+fn can_serialize_finding() {
+    let finding = Finding::StargateNotSymmetric {
+        solarsystem: Solarsystem::Plagar,
+        target: Solarsystem::Vosu,
+    };
+    let json = serde_json::to_string(&finding).unwrap();
+    assert_eq!(
+        json,
+        r#"{"kind":"stargateNotSymmetric","details":{"solarsystem":"Plagar","target":"Vosu"}}"#
+    );
+}
+
+#[test]
+fn finds_solarsystem_without_constellation() {
+    let mut statics = Statics::default();
+    statics
+        .constellations
+        .data
+        .get_mut(&Constellation::Abyss)
+        .unwrap()
+        .solarsystems
+        .clear();
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::SolarsystemWithoutConstellation(Solarsystem::Vosu)]
+    );
+}
+
+#[test]
+fn finds_constellation_in_multiple_regions() {
+    let mut statics = Statics::default();
+    statics
+        .regions
+        .data
+        .get_mut(&Region::Heartland)
+        .unwrap()
+        .constellations
+        .push(Constellation::Abyss);
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::ConstellationInMultipleRegions(
+            Constellation::Abyss
+        )]
+    );
+}
+
+#[test]
+fn finds_unknown_solarsystem_in_constellation() {
+    let mut statics = Statics::default();
+    statics.solarsystems.data.remove(&Solarsystem::Vosu);
+    for details in statics.solarsystems.data.values_mut() {
+        details.stargates.remove(&Solarsystem::Vosu);
+    }
+    statics.localizations.data.remove(&Solarsystem::Vosu.into());
+    let findings = statics.validate();
+    assert_eq!(
+        findings,
+        vec![Finding::ConstellationUnknownSolarsystem {
+            constellation: Constellation::Abyss,
+            solarsystem: Solarsystem::Vosu,
+        }]
+    );
+}
+
+#[test]
+fn finds_too_many_stations() {
+    let mut statics = Statics::default();
+    let details = statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap();
+    details.stations = vec![1; 257];
+    let findings = statics.validate();
+    assert_eq!(findings, vec![Finding::TooManyStations(Solarsystem::Vosu)]);
+}
+
+#[test]
+fn findings_have_messages() {
+    assert_eq!(
+        Finding::ItemWithoutVolume(Mineral::Derite.into()).to_string(),
+        "item Derite has no volume"
+    );
+    assert_eq!(
+        Finding::StargateNotSymmetric {
+            solarsystem: Solarsystem::Plagar,
+            target: Solarsystem::Vosu,
+        }
+        .to_string(),
+        "stargate Plagar -> Vosu has no stargate back"
+    );
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use crate::fixed::{facility, item, localization, module, region, shiplayout, solarsystem};
+use crate::player::{location::PlayerLocation, General, Notifications, StationAssets};
+use crate::site::entity_frontread::SiteEntity;
+use crate::{site, station};
+
+/// JSON Schemas of every static data file and the types send between server and clients.
+///
+/// Key: Name of the file without extension. Static data files are named like their file in the `static` folder.
+#[must_use]
+pub fn all() -> BTreeMap<&'static str, RootSchema> {
+    let mut result = BTreeMap::new();
+
+    result.insert(
+        "constellation",
+        schema_for!(BTreeMap<region::Constellation, region::ConstellationDetails>),
+    );
+    result.insert(
+        "facility",
+        schema_for!(BTreeMap<facility::Facility, facility::Details>),
+    );
+    result.insert("item", schema_for!(BTreeMap<item::Item, item::Details>));
+    result.insert(
+        "localization",
+        schema_for!(BTreeMap<localization::Localizable, localization::Details>),
+    );
+    result.insert(
+        "module-passive",
+        schema_for!(BTreeMap<module::Passive, module::PassiveDetails>),
+    );
+    result.insert(
+        "module-targeted",
+        schema_for!(BTreeMap<module::Targeted, module::TargetedDetails>),
+    );
+    result.insert(
+        "module-untargeted",
+        schema_for!(BTreeMap<module::Untargeted, module::UntargetedDetails>),
+    );
+    result.insert(
+        "region",
+        schema_for!(BTreeMap<region::Region, region::RegionDetails>),
+    );
+    result.insert(
+        "ship-layout",
+        schema_for!(BTreeMap<shiplayout::ShipLayout, shiplayout::Details>),
+    );
+    result.insert(
+        "solarsystem",
+        schema_for!(BTreeMap<solarsystem::Solarsystem, solarsystem::Details>),
+    );
+
+    result.insert("player-general", schema_for!(General));
+    result.insert("player-location", schema_for!(PlayerLocation));
+    result.insert("player-notifications", schema_for!(Notifications));
+    result.insert("player-station-assets", schema_for!(StationAssets));
+    result.insert("route", schema_for!(crate::universe::Route));
+    result.insert("site-entity", schema_for!(SiteEntity));
+    result.insert(
+        "site-instruction",
+        schema_for!(site::instruction::Instruction),
+    );
+    result.insert("site-log", schema_for!(site::Log));
+    result.insert(
+        "solarsystem-state",
+        schema_for!(crate::universe::SolarsystemState),
+    );
+    result.insert(
+        "station-instruction",
+        schema_for!(station::instruction::Instruction),
+    );
+    result.insert("wormhole", schema_for!(crate::universe::Wormhole));
+
+    result
+}
+
+#[test]
+fn export() -> anyhow::Result<()> {
+    for (name, schema) in all() {
+        let json = serde_json::to_string_pretty(&schema)?;
+        let path = format!("static/{}.schema.json", name);
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        if current != json {
+            std::fs::write(path, json)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn ship_layout_schema_contains_details() {
+    let schemas = all();
+    let schema = serde_json::to_value(&schemas["ship-layout"]).unwrap();
+    let details = &schema["definitions"]["ShipLayoutDetails"];
+    assert_eq!(details["type"], "object");
+    assert!(details["properties"].get("cargoVolume").is_some());
+    assert!(details["properties"].get("structure").is_some());
+}
@@ -8,12 +8,18 @@ pub mod entity;
 /// Static data which is read-only for everyone.
 /// It only changes on game-updates.
 pub mod fixed;
+/// JSON Schemas for non TypeScript clients and editors of the static data.
+#[cfg(feature = "schemars")]
+pub mod json_schema;
 pub mod market;
 pub mod player;
+mod random;
 pub mod ship;
 pub mod site;
 pub mod station;
 pub mod storage;
+/// The solarsystems and how they are connected.
+pub mod universe;
 
 #[cfg(test)]
 mod test_helper;
@@ -0,0 +1,60 @@
+use crate::fixed::item::Item;
+
+use super::{OrderId, Trader};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownOrder(OrderId),
+    /// Ids are unique across all markets
+    DuplicateOrder(OrderId),
+    /// Only the trader which issued the order can change it
+    NotIssuer {
+        order: OrderId,
+        trader: Trader,
+    },
+    /// Amount and paperclips have to be above 0 without overflowing the total
+    InvalidOrder,
+    NotEnoughPaperclips {
+        required: u64,
+        available: u64,
+    },
+    NotEnoughItems {
+        item: Item,
+        required: u32,
+        available: u32,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOrder(order) => write!(f, "Order {} does not exist.", order),
+            Self::DuplicateOrder(order) => write!(f, "Order {} already exists.", order),
+            Self::NotIssuer { order, trader } => write!(
+                f,
+                "Order {} was not issued by {:?} and can not be changed by them.",
+                order, trader
+            ),
+            Self::InvalidOrder => write!(f, "Order needs an amount and paperclips."),
+            Self::NotEnoughPaperclips {
+                required,
+                available,
+            } => write!(
+                f,
+                "Requires {} paperclips but only {} are available.",
+                required, available
+            ),
+            Self::NotEnoughItems {
+                item,
+                required,
+                available,
+            } => write!(
+                f,
+                "Requires {} {:?} but only {} are available.",
+                required, item, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,529 @@
+use crate::fixed::item::Item;
+use crate::player::{General, StationAssets};
+
+use super::{Escrow, FeeRates, Market, MarketError, Order, OrderId, Side, Trade, Trader};
+
+fn take_paperclips(general: &mut General, paperclips: u64) -> Result<(), MarketError> {
+    general.paperclips =
+        general
+            .paperclips
+            .checked_sub(paperclips)
+            .ok_or(MarketError::NotEnoughPaperclips {
+                required: paperclips,
+                available: general.paperclips,
+            })?;
+    Ok(())
+}
+
+fn take_items(assets: &mut StationAssets, item: Item, amount: u32) -> Result<(), MarketError> {
+    let available = assets.storage.amount(item);
+    if assets.storage.take_exact(item, amount) {
+        Ok(())
+    } else {
+        Err(MarketError::NotEnoughItems {
+            item,
+            required: amount,
+            available,
+        })
+    }
+}
+
+/// Takes what the order needs from the issuer: paperclips for buy orders, items for sell orders.
+/// `assets` are the ones at the station of the order.
+/// # Errors
+/// Errors when the issuer does not have enough. Nothing is taken then.
+pub fn reserve(
+    item: Item,
+    side: Side,
+    order: &Order,
+    general: &mut General,
+    assets: &mut StationAssets,
+) -> Result<Escrow, MarketError> {
+    let escrow = order.escrow(side);
+    match escrow {
+        Escrow::Paperclips(paperclips) => take_paperclips(general, paperclips)?,
+        Escrow::Items(amount) => take_items(assets, item, amount)?,
+    }
+    Ok(escrow)
+}
+
+/// Takes the escrow and the broker fee of a new order from the issuer.
+/// Returns the broker fee which was charged.
+/// # Errors
+/// Errors when the order is not valid or the issuer can not pay for it. Nothing is taken then.
+pub fn place(
+    item: Item,
+    side: Side,
+    order: &Order,
+    rates: &FeeRates,
+    general: &mut General,
+    assets: &mut StationAssets,
+) -> Result<u64, MarketError> {
+    if !order.is_valid() {
+        return Err(MarketError::InvalidOrder);
+    }
+    let fee = rates.broker_fee(order);
+    let required = match order.escrow(side) {
+        Escrow::Paperclips(paperclips) => paperclips.saturating_add(fee),
+        Escrow::Items(_) => fee,
+    };
+    if general.paperclips < required {
+        return Err(MarketError::NotEnoughPaperclips {
+            required,
+            available: general.paperclips,
+        });
+    }
+    reserve(item, side, order, general, assets)?;
+    take_paperclips(general, fee)?;
+    Ok(fee)
+}
+
+/// Gives back what is still reserved for an order which is removed unfulfilled like a cancelled or expired one.
+pub fn release(
+    item: Item,
+    side: Side,
+    order: &Order,
+    general: &mut General,
+    assets: &mut StationAssets,
+) {
+    match order.escrow(side) {
+        Escrow::Paperclips(paperclips) => {
+            general.paperclips = general.paperclips.saturating_add(paperclips);
+        }
+        Escrow::Items(amount) => assets.storage.saturating_add(item, amount),
+    }
+}
+
+/// Adapts the escrow when an order is modified from `before` to `after`.
+/// Prefer [`modify`] which also changes the order in the market so both can not diverge.
+/// # Errors
+/// Errors when the issuer does not have enough for the bigger order. Nothing is taken then.
+pub fn change(
+    item: Item,
+    side: Side,
+    before: &Order,
+    after: &Order,
+    general: &mut General,
+    assets: &mut StationAssets,
+) -> Result<(), MarketError> {
+    match (before.escrow(side), after.escrow(side)) {
+        (Escrow::Paperclips(before), Escrow::Paperclips(after)) if after > before => {
+            take_paperclips(general, after - before)?;
+        }
+        (Escrow::Paperclips(before), Escrow::Paperclips(after)) => {
+            general.paperclips = general.paperclips.saturating_add(before - after);
+        }
+        (Escrow::Items(before), Escrow::Items(after)) if after > before => {
+            take_items(assets, item, after - before)?;
+        }
+        (Escrow::Items(before), Escrow::Items(after)) => {
+            assets.storage.saturating_add(item, before - after);
+        }
+        _ => unreachable!("escrow of the same side is always of the same kind"),
+    }
+    Ok(())
+}
+
+/// Modifies an order in the market and adapts its escrow at the same time.
+/// The broker fee is charged on the added total paperclips only.
+/// `assets` are the ones at the station of the order.
+/// Returns the broker fee which was charged.
+/// # Errors
+/// Errors when the order does not exist, was issued by someone else, the change is not valid
+/// or the issuer can not pay for it. Neither the market nor the issuer are changed then.
+#[allow(clippy::too_many_arguments)]
+pub fn modify(
+    market: &mut Market,
+    item: Item,
+    trader: Trader,
+    id: OrderId,
+    amount: u32,
+    paperclips: u64,
+    rates: &FeeRates,
+    general: &mut General,
+    assets: &mut StationAssets,
+) -> Result<u64, MarketError> {
+    let (side, before) = market
+        .item(item)
+        .ok_or(MarketError::UnknownOrder(id))?
+        .issued(trader, id)?;
+    let after = Order {
+        amount,
+        paperclips,
+        ..before
+    };
+    if !after.is_valid() {
+        return Err(MarketError::InvalidOrder);
+    }
+
+    let fee = rates.broker_fee_of_change(&before, &after);
+    let (required_paperclips, required_items) = match (before.escrow(side), after.escrow(side)) {
+        (Escrow::Paperclips(before), Escrow::Paperclips(after)) => {
+            (after.saturating_sub(before).saturating_add(fee), 0)
+        }
+        (Escrow::Items(before), Escrow::Items(after)) => (fee, after.saturating_sub(before)),
+        _ => unreachable!("escrow of the same side is always of the same kind"),
+    };
+    if general.paperclips < required_paperclips {
+        return Err(MarketError::NotEnoughPaperclips {
+            required: required_paperclips,
+            available: general.paperclips,
+        });
+    }
+    let available_items = assets.storage.amount(item);
+    if available_items < required_items {
+        return Err(MarketError::NotEnoughItems {
+            item,
+            required: required_items,
+            available: available_items,
+        });
+    }
+
+    market.modify(item, trader, id, amount, paperclips)?;
+    change(item, side, &before, &after, general, assets)?;
+    take_paperclips(general, fee)?;
+    Ok(fee)
+}
+
+impl Trade {
+    /// Moves the bought items into the station storage of the buyer and gives back the `buyer_refund`.
+    /// The paperclips were already taken by the escrow of the buy order.
+    pub fn settle_buyer(&self, item: Item, general: &mut General, assets: &mut StationAssets) {
+        assets.storage.saturating_add(item, self.amount);
+        general.paperclips = general.paperclips.saturating_add(self.buyer_refund);
+    }
+
+    /// Pays the seller the paperclips after the sales tax.
+    /// The items were already taken by the escrow of the sell order.
+    pub fn settle_seller(&self, general: &mut General) {
+        general.paperclips = general.paperclips.saturating_add(self.net_paperclips());
+    }
+}
+
+#[cfg(test)]
+fn player_with(paperclips: u64, aromit: u32) -> (General, StationAssets) {
+    use crate::fixed::item::Ore;
+    let general = General {
+        paperclips,
+        ..General::default()
+    };
+    let assets = StationAssets {
+        storage: crate::storage::Storage::new_single(Ore::Aromit, aromit),
+        ..StationAssets::default()
+    };
+    (general, assets)
+}
+
+#[test]
+fn reserve_takes_paperclips_for_buy() {
+    use crate::fixed::item::Ore;
+    let (mut general, mut assets) = player_with(1000, 0);
+    let order = Order::new_test_simple(10, 42);
+    let escrow = reserve(
+        Ore::Aromit.into(),
+        Side::Buy,
+        &order,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(escrow, Ok(Escrow::Paperclips(420)));
+    assert_eq!(general.paperclips, 580);
+
+    release(
+        Ore::Aromit.into(),
+        Side::Buy,
+        &order,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(general.paperclips, 1000);
+}
+
+#[test]
+fn reserve_takes_items_for_sell() {
+    use crate::fixed::item::Ore;
+    let (mut general, mut assets) = player_with(0, 12);
+    let order = Order::new_test_simple(10, 42);
+    let escrow = reserve(
+        Ore::Aromit.into(),
+        Side::Sell,
+        &order,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(escrow, Ok(Escrow::Items(10)));
+    assert_eq!(assets.storage.amount(Ore::Aromit), 2);
+
+    release(
+        Ore::Aromit.into(),
+        Side::Sell,
+        &order,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(assets.storage.amount(Ore::Aromit), 12);
+}
+
+#[test]
+fn reserve_takes_nothing_when_not_enough() {
+    use crate::fixed::item::Ore;
+    let (mut general, mut assets) = player_with(100, 5);
+    let order = Order::new_test_simple(10, 42);
+    assert_eq!(
+        reserve(
+            Ore::Aromit.into(),
+            Side::Buy,
+            &order,
+            &mut general,
+            &mut assets
+        ),
+        Err(MarketError::NotEnoughPaperclips {
+            required: 420,
+            available: 100,
+        })
+    );
+    assert_eq!(
+        reserve(
+            Ore::Aromit.into(),
+            Side::Sell,
+            &order,
+            &mut general,
+            &mut assets
+        ),
+        Err(MarketError::NotEnoughItems {
+            item: Ore::Aromit.into(),
+            required: 10,
+            available: 5,
+        })
+    );
+    assert_eq!((general, assets), player_with(100, 5));
+}
+
+#[test]
+fn place_needs_escrow_and_fee() {
+    use crate::fixed::item::Ore;
+    let rates = FeeRates {
+        broker_fee: 1000,
+        sales_tax: 0,
+    };
+    let order = Order::new_test_simple(10, 100);
+
+    let (mut general, mut assets) = player_with(1050, 0);
+    let result = place(
+        Ore::Aromit.into(),
+        Side::Buy,
+        &order,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(
+        result,
+        Err(MarketError::NotEnoughPaperclips {
+            required: 1100,
+            available: 1050,
+        })
+    );
+    assert_eq!(general.paperclips, 1050);
+
+    let (mut general, mut assets) = player_with(150, 10);
+    let result = place(
+        Ore::Aromit.into(),
+        Side::Sell,
+        &order,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(result, Ok(100));
+    assert_eq!(general.paperclips, 50);
+    assert_eq!(assets.storage.amount(Ore::Aromit), 0);
+}
+
+#[test]
+fn change_reserves_difference() {
+    use crate::fixed::item::Ore;
+    let (mut general, mut assets) = player_with(1000, 0);
+    let before = Order::new_test_simple(10, 42);
+    let after = Order::new_test_simple(10, 50);
+    change(
+        Ore::Aromit.into(),
+        Side::Buy,
+        &before,
+        &after,
+        &mut general,
+        &mut assets,
+    )
+    .unwrap();
+    assert_eq!(general.paperclips, 920);
+    change(
+        Ore::Aromit.into(),
+        Side::Buy,
+        &after,
+        &before.reduce_to(5),
+        &mut general,
+        &mut assets,
+    )
+    .unwrap();
+    assert_eq!(general.paperclips, 1210);
+}
+
+#[test]
+fn modify_changes_market_and_escrow_together() {
+    use crate::fixed::item::Ore;
+    let item: Item = Ore::Aromit.into();
+    let trader = Trader::Player(crate::player::Player::Telegram(666));
+    let rates = FeeRates {
+        broker_fee: 1000,
+        sales_tax: 0,
+    };
+    let mut order = Order::new_test_simple(10, 100);
+    order.id = 1;
+    order.trader = trader;
+    let mut market = Market::default();
+    market.place(item, Side::Buy, order).unwrap();
+    let (mut general, mut assets) = player_with(1000, 0);
+
+    // 500 more escrow and 50 broker fee on the added 500
+    let result = modify(
+        &mut market,
+        item,
+        trader,
+        1,
+        15,
+        100,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(result, Ok(50));
+    assert_eq!(general.paperclips, 450);
+    let (_, modified) = market.item(item).unwrap().get(1).unwrap();
+    assert_eq!(modified.amount, 15);
+
+    // Not enough for the escrow and the fee, nothing changes
+    let result = modify(
+        &mut market,
+        item,
+        trader,
+        1,
+        20,
+        100,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(
+        result,
+        Err(MarketError::NotEnoughPaperclips {
+            required: 550,
+            available: 450,
+        })
+    );
+    assert_eq!(general.paperclips, 450);
+    assert_eq!(market.item(item).unwrap().get(1).unwrap().1.amount, 15);
+
+    // Reducing returns the escrow without a fee
+    let result = modify(
+        &mut market,
+        item,
+        trader,
+        1,
+        5,
+        100,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(result, Ok(0));
+    assert_eq!(general.paperclips, 1450);
+}
+
+#[test]
+fn modify_needs_items_for_sell() {
+    use crate::fixed::item::Ore;
+    let item: Item = Ore::Aromit.into();
+    let trader = Trader::Player(crate::player::Player::Telegram(666));
+    let rates = FeeRates::default();
+    let mut order = Order::new_test_simple(10, 100);
+    order.id = 1;
+    order.trader = trader;
+    let mut market = Market::default();
+    market.place(item, Side::Sell, order).unwrap();
+    let (mut general, mut assets) = player_with(0, 5);
+
+    let result = modify(
+        &mut market,
+        item,
+        trader,
+        1,
+        20,
+        100,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(
+        result,
+        Err(MarketError::NotEnoughItems {
+            item,
+            required: 10,
+            available: 5,
+        })
+    );
+    assert_eq!(market.item(item).unwrap().get(1).unwrap().1.amount, 10);
+
+    let result = modify(
+        &mut market,
+        item,
+        trader,
+        1,
+        15,
+        100,
+        &rates,
+        &mut general,
+        &mut assets,
+    );
+    assert_eq!(result, Ok(0));
+    assert_eq!(assets.storage.amount(Ore::Aromit), 0);
+}
+
+#[test]
+fn settlement_moves_goods_and_paperclips() {
+    use crate::fixed::item::Ore;
+    use crate::fixed::solarsystem::Solarsystem;
+    use crate::market::ItemMarket;
+    let item: Item = Ore::Aromit.into();
+    let rates = FeeRates {
+        broker_fee: 0,
+        sales_tax: 1000,
+    };
+    let (mut buyer, mut buyer_assets) = player_with(1000, 0);
+    let (mut seller, mut seller_assets) = player_with(0, 10);
+
+    let sell = Order::new_test("1997-12-19T16:00:00Z", Solarsystem::default(), 10, 40);
+    let buy = Order::new_test("1997-12-19T17:00:00Z", Solarsystem::default(), 5, 50);
+    place(
+        item,
+        Side::Sell,
+        &sell,
+        &rates,
+        &mut seller,
+        &mut seller_assets,
+    )
+    .unwrap();
+    place(item, Side::Buy, &buy, &rates, &mut buyer, &mut buyer_assets).unwrap();
+    assert_eq!(buyer.paperclips, 750);
+
+    let mut market = ItemMarket::new(vec![buy], vec![sell]);
+    let trades = market.resolve();
+    assert_eq!(trades.len(), 1);
+    let trade = rates.apply_sales_tax(trades[0]);
+    trade.settle_buyer(item, &mut buyer, &mut buyer_assets);
+    trade.settle_seller(&mut seller);
+
+    assert_eq!(buyer.paperclips, 800);
+    assert_eq!(buyer_assets.storage.amount(Ore::Aromit), 5);
+    assert_eq!(seller.paperclips, 180);
+    assert_eq!(seller_assets.storage.amount(Ore::Aromit), 0);
+}
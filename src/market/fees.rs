@@ -0,0 +1,193 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{MarketError, Order, Trade};
+
+/// 100 % in basis points
+pub const MAX_RATE: u16 = 10_000;
+
+/// Rates in basis points: 100 is 1 %.
+/// Rates above [`MAX_RATE`] are rejected when deserializing and capped when fees are calculated.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRates {
+    /// Basis points charged from the issuer when an order is placed, based on the total paperclips of the order
+    #[serde(deserialize_with = "basis_points")]
+    pub broker_fee: u16,
+    /// Basis points charged from the seller on every trade
+    #[serde(deserialize_with = "basis_points")]
+    pub sales_tax: u16,
+}
+
+fn basis_points<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+    let rate = u16::deserialize(deserializer)?;
+    if rate > MAX_RATE {
+        return Err(D::Error::custom(format!(
+            "rate of {} basis points is above {}",
+            rate, MAX_RATE
+        )));
+    }
+    Ok(rate)
+}
+
+/// Fees depend on the security of the solarsystem the station is in.
+/// Rates in between are interpolated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeConfig {
+    /// Rates at a security of 100
+    pub high_security: FeeRates,
+    /// Rates at a security of 0
+    pub low_security: FeeRates,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            high_security: FeeRates {
+                broker_fee: 100,
+                sales_tax: 200,
+            },
+            low_security: FeeRates {
+                broker_fee: 300,
+                sales_tax: 800,
+            },
+        }
+    }
+}
+
+impl FeeConfig {
+    /// Rates at the given security, never above [`MAX_RATE`]
+    #[must_use]
+    pub fn rates(&self, security: u8) -> FeeRates {
+        let interpolate = |high: u16, low: u16| {
+            let security = i32::from(security.min(100));
+            let rate = i32::from(low) + (i32::from(high) - i32::from(low)) * security / 100;
+            u16::try_from(rate).unwrap_or(MAX_RATE).min(MAX_RATE)
+        };
+        FeeRates {
+            broker_fee: interpolate(self.high_security.broker_fee, self.low_security.broker_fee),
+            sales_tax: interpolate(self.high_security.sales_tax, self.low_security.sales_tax),
+        }
+    }
+}
+
+/// Partial paperclips are always rounded up so every trade pays something.
+/// Rates above [`MAX_RATE`] are capped so a fee is never more than the total.
+fn fee(total: u64, rate: u16) -> u64 {
+    let rate = rate.min(MAX_RATE);
+    let fee = (u128::from(total) * u128::from(rate)).div_ceil(u128::from(MAX_RATE));
+    fee.try_into().unwrap_or(u64::MAX)
+}
+
+impl FeeRates {
+    #[must_use]
+    pub fn broker_fee(&self, order: &Order) -> u64 {
+        fee(order.total_paperclips(), self.broker_fee)
+    }
+
+    /// Broker fee of modifying an order which is only charged on the added total paperclips
+    #[must_use]
+    pub fn broker_fee_of_change(&self, before: &Order, after: &Order) -> u64 {
+        let added = after
+            .total_paperclips()
+            .saturating_sub(before.total_paperclips());
+        fee(added, self.broker_fee)
+    }
+
+    /// Takes the broker fee of the order from the paperclips of the issuer.
+    /// # Errors
+    /// Errors when the issuer can not pay the fee. Nothing is taken then.
+    pub fn charge_broker_fee(&self, order: &Order, wallet: &mut u64) -> Result<u64, MarketError> {
+        let fee = self.broker_fee(order);
+        *wallet = wallet
+            .checked_sub(fee)
+            .ok_or(MarketError::NotEnoughPaperclips {
+                required: fee,
+                available: *wallet,
+            })?;
+        Ok(fee)
+    }
+
+    /// The trade with its `sales_tax` set
+    #[must_use]
+    pub fn apply_sales_tax(&self, trade: Trade) -> Trade {
+        Trade {
+            sales_tax: fee(trade.total_paperclips(), self.sales_tax),
+            ..trade
+        }
+    }
+}
+
+#[test]
+fn rates_depend_on_security() {
+    let config = FeeConfig::default();
+    assert_eq!(config.rates(100), config.high_security);
+    assert_eq!(config.rates(0), config.low_security);
+    assert_eq!(
+        config.rates(50),
+        FeeRates {
+            broker_fee: 200,
+            sales_tax: 500,
+        }
+    );
+}
+
+#[test]
+fn rates_are_at_most_100_percent() {
+    let config = FeeConfig {
+        high_security: FeeRates {
+            broker_fee: u16::MAX,
+            sales_tax: 0,
+        },
+        low_security: FeeRates::default(),
+    };
+    assert_eq!(config.rates(100).broker_fee, MAX_RATE);
+
+    let parsed = serde_json::from_str::<FeeRates>(r#"{"brokerFee":10000,"salesTax":200}"#);
+    assert_eq!(
+        parsed.unwrap(),
+        FeeRates {
+            broker_fee: 10_000,
+            sales_tax: 200,
+        }
+    );
+    let parsed = serde_json::from_str::<FeeRates>(r#"{"brokerFee":10001,"salesTax":200}"#);
+    assert!(parsed.is_err());
+}
+
+#[test]
+fn fees_round_up() {
+    assert_eq!(fee(0, 200), 0);
+    assert_eq!(fee(1, 200), 1);
+    assert_eq!(fee(10_000, 200), 200);
+    assert_eq!(fee(10_001, 200), 201);
+    assert_eq!(fee(u64::MAX, 10_000), u64::MAX);
+    assert_eq!(fee(100, 20_000), 100);
+}
+
+#[test]
+fn charge_broker_fee_from_wallet() {
+    let rates = FeeConfig::default().rates(100);
+    let order = Order::new_test_simple(10, 1000);
+    let mut wallet = 150;
+    assert_eq!(rates.charge_broker_fee(&order, &mut wallet), Ok(100));
+    assert_eq!(wallet, 50);
+    assert_eq!(
+        rates.charge_broker_fee(&order, &mut wallet),
+        Err(MarketError::NotEnoughPaperclips {
+            required: 100,
+            available: 50,
+        })
+    );
+    assert_eq!(wallet, 50);
+}
+
+#[test]
+fn sales_tax_reduces_net() {
+    let rates = FeeConfig::default().rates(0);
+    let trade = rates.apply_sales_tax(Trade::new_test(10, 100));
+    assert_eq!(trade.sales_tax, 80);
+    assert_eq!(trade.total_paperclips(), 1000);
+    assert_eq!(trade.net_paperclips(), 920);
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::fixed::item::Item;
+use crate::serde_helper::ordered_map;
+
+use super::{ItemMarket, MarketError, Order, OrderId, PricingRule, Side, Trade, Trader};
+
+/// All the item markets of a single station.
+/// Markets without orders are removed.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Market(#[serde(serialize_with = "ordered_map")] HashMap<Item, ItemMarket>);
+
+impl Market {
+    /// Adds the order to the market of the item
+    /// # Errors
+    /// Errors when the order has no amount, paperclips or id or its id is already used by another order.
+    /// The id 0 is only valid for orders loaded from before ids existed.
+    pub fn place(&mut self, item: Item, side: Side, order: Order) -> Result<OrderId, MarketError> {
+        if order.id == 0 || !order.is_valid() {
+            return Err(MarketError::InvalidOrder);
+        }
+        if self.0.values().any(|market| market.get(order.id).is_some()) {
+            return Err(MarketError::DuplicateOrder(order.id));
+        }
+        self.0.entry(item).or_default().insert(side, order);
+        Ok(order.id)
+    }
+
+    /// See [`ItemMarket::cancel`]
+    /// # Errors
+    /// Errors when the order does not exist or was issued by someone else
+    pub fn cancel(
+        &mut self,
+        item: Item,
+        trader: Trader,
+        id: OrderId,
+    ) -> Result<(Side, Order), MarketError> {
+        let market = self.0.get_mut(&item).ok_or(MarketError::UnknownOrder(id))?;
+        let result = market.cancel(trader, id);
+        if market.is_empty() {
+            self.0.remove(&item);
+        }
+        result
+    }
+
+    /// See [`ItemMarket::modify`]
+    /// # Errors
+    /// Errors when the order does not exist, was issued by someone else or the change is not valid
+    pub fn modify(
+        &mut self,
+        item: Item,
+        trader: Trader,
+        id: OrderId,
+        amount: u32,
+        paperclips: u64,
+    ) -> Result<(Side, Order), MarketError> {
+        let market = self.0.get_mut(&item).ok_or(MarketError::UnknownOrder(id))?;
+        let result = market.modify(trader, id, amount, paperclips);
+        if market.is_empty() {
+            self.0.remove(&item);
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn item(&self, item: Item) -> Option<&ItemMarket> {
+        self.0.get(&item).filter(|o| !o.is_empty())
+    }
+
+    /// Items which currently have orders
+    #[must_use]
+    pub fn items(&self) -> Vec<Item> {
+        let mut items = self
+            .0
+            .iter()
+            .filter(|(_, market)| !market.is_empty())
+            .map(|(item, _)| *item)
+            .collect::<Vec<_>>();
+        items.sort();
+        items
+    }
+
+    /// All the orders issued by the trader
+    #[must_use]
+    pub fn orders_of(&self, trader: Trader) -> Vec<(Item, Side, Order)> {
+        let mut result = Vec::new();
+        for item in self.items() {
+            let market = &self.0[&item];
+            let buy_orders = market.buy_orders().map(|o| (Side::Buy, o));
+            let sell_orders = market.sell_orders().map(|o| (Side::Sell, o));
+            for (side, order) in buy_orders.chain(sell_orders) {
+                if order.trader == trader {
+                    result.push((item, side, *order));
+                }
+            }
+        }
+        result
+    }
+
+    /// See [`ItemMarket::remove_expired`]
+    pub fn remove_expired(&mut self, now: DateTime<Utc>) -> Vec<(Item, Side, Order)> {
+        let mut result = Vec::new();
+        for (item, market) in &mut self.0 {
+            for (side, order) in market.remove_expired(now) {
+                result.push((*item, side, order));
+            }
+        }
+        self.0.retain(|_, market| !market.is_empty());
+        // Stable sort keeps the order within an item
+        result.sort_by_key(|(item, _, _)| *item);
+        result
+    }
+
+    /// Resolves the markets of all items with the [`PricingRule::default`].
+    /// The trades can be passed to the `player::Notifications` of the buyer and seller.
+    /// The trades are not taxed yet, see [`super::FeeRates::apply_sales_tax`].
+    pub fn resolve_all(&mut self) -> Vec<(Item, Trade)> {
+        self.resolve_all_with(PricingRule::default())
+    }
+
+    /// Resolves the markets of all items with the given pricing rule.
+    /// See [`ItemMarket::resolve_with`].
+    pub fn resolve_all_with(&mut self, pricing: PricingRule) -> Vec<(Item, Trade)> {
+        let mut result = Vec::new();
+        for (item, market) in &mut self.0 {
+            for trade in market.resolve_with(pricing) {
+                result.push((*item, trade));
+            }
+        }
+        self.0.retain(|_, market| !market.is_empty());
+        // Stable sort keeps the order of the trades within an item
+        result.sort_by_key(|(item, _)| *item);
+        result
+    }
+}
+
+#[cfg(test)]
+fn player() -> Trader {
+    Trader::Player(crate::player::Player::Telegram(666))
+}
+
+#[cfg(test)]
+fn example() -> Market {
+    use crate::fixed::item::{Mineral, Ore};
+    let mut market = Market::default();
+    let mut sell = Order::example_c();
+    sell.id = 1;
+    market.place(Ore::Aromit.into(), Side::Sell, sell).unwrap();
+    let mut buy = Order::example_a();
+    buy.id = 2;
+    buy.trader = player();
+    market.place(Ore::Aromit.into(), Side::Buy, buy).unwrap();
+    let mut buy = Order::example_b();
+    buy.id = 3;
+    buy.trader = player();
+    market
+        .place(Mineral::Derite.into(), Side::Buy, buy)
+        .unwrap();
+    market
+}
+
+#[test]
+fn can_serde_parse() {
+    crate::test_helper::can_serde_parse(&example());
+}
+
+#[test]
+fn place_rejects_invalid() {
+    let mut market = Market::default();
+    let mut order = Order::new_test_simple(0, 42);
+    order.id = 1;
+    let result = market.place(Item::EXAMPLE, Side::Buy, order);
+    assert_eq!(result, Err(MarketError::InvalidOrder));
+    assert_eq!(market.items(), []);
+}
+
+#[test]
+fn place_rejects_missing_id() {
+    let mut market = Market::default();
+    let result = market.place(Item::EXAMPLE, Side::Buy, Order::new_test_simple(1, 42));
+    assert_eq!(result, Err(MarketError::InvalidOrder));
+    assert_eq!(market.items(), []);
+}
+
+#[test]
+fn place_rejects_duplicate_id() {
+    use crate::fixed::item::Mineral;
+    let mut market = example();
+    let mut order = Order::example_b();
+    order.id = 1;
+    let result = market.place(Mineral::Derite.into(), Side::Sell, order);
+    assert_eq!(result, Err(MarketError::DuplicateOrder(1)));
+    assert_eq!(market, example());
+}
+
+#[test]
+fn query_orders_of_trader() {
+    use crate::fixed::item::{Mineral, Ore};
+    let market = example();
+    assert_eq!(market.items(), [Mineral::Derite.into(), Ore::Aromit.into()]);
+    let orders = market
+        .orders_of(player())
+        .iter()
+        .map(|(item, side, order)| (*item, *side, order.id))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        orders,
+        [
+            (Mineral::Derite.into(), Side::Buy, 3),
+            (Ore::Aromit.into(), Side::Buy, 2),
+        ]
+    );
+}
+
+#[test]
+fn cancel_in_other_item_is_unknown() {
+    use crate::fixed::item::{Mineral, Ore};
+    let mut market = example();
+    assert_eq!(
+        market.cancel(Ore::Solmit.into(), player(), 2),
+        Err(MarketError::UnknownOrder(2))
+    );
+    assert_eq!(
+        market.cancel(Mineral::Derite.into(), player(), 2),
+        Err(MarketError::UnknownOrder(2))
+    );
+    let (side, order) = market.cancel(Ore::Aromit.into(), player(), 2).unwrap();
+    assert_eq!(side, Side::Buy);
+    assert_eq!(order.id, 2);
+}
+
+#[test]
+fn cancel_removes_empty_market() {
+    use crate::fixed::item::{Mineral, Ore};
+    let mut market = example();
+    market.cancel(Mineral::Derite.into(), player(), 3).unwrap();
+    assert_eq!(market.items(), [Ore::Aromit.into()]);
+    assert!(!market.0.contains_key(&Mineral::Derite.into()));
+}
+
+#[test]
+fn resolve_all_items() {
+    use crate::fixed::item::Ore;
+    let mut market = example();
+    let trades = market.resolve_all();
+    assert_eq!(trades.len(), 1);
+    let (item, trade) = trades[0];
+    assert_eq!(item, Ore::Aromit.into());
+    assert_eq!(trade.buyer, player());
+    assert_eq!(trade.amount, 42);
+
+    let notifications = crate::player::Notifications {
+        trades,
+        ..crate::player::Notifications::default()
+    };
+    assert_eq!(notifications.trades[0].0, Ore::Aromit.into());
+
+    let aromit = market.item(Ore::Aromit.into()).unwrap();
+    assert_eq!(aromit.buy_orders().count(), 0);
+    assert_eq!(aromit.sell_orders().next().unwrap().amount, 666 - 42);
+}
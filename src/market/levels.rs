@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Order, OrderId};
+
+/// Orders of one side of the market grouped by their price.
+///
+/// Each price level keeps its orders sorted by date so older orders are handled first.
+/// Empty levels are never kept.
+///
+/// The price of every order is indexed by its id so a single order is found without looking at all of them.
+/// Orders from before ids existed (id 0) are not indexed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Levels {
+    levels: BTreeMap<u64, VecDeque<Order>>,
+    prices: HashMap<OrderId, u64>,
+}
+
+impl Levels {
+    /// Puts the order behind all orders with the same price which are not newer
+    pub fn insert(&mut self, order: Order) {
+        let level = self.levels.entry(order.paperclips).or_default();
+        let index = level.partition_point(|o| o.date <= order.date);
+        level.insert(index, order);
+        if order.id != 0 {
+            self.prices.insert(order.id, order.paperclips);
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    #[must_use]
+    pub fn lowest_price(&self) -> Option<u64> {
+        self.levels.keys().next().copied()
+    }
+
+    /// Lowest price first
+    pub fn ascending(&self) -> impl DoubleEndedIterator<Item = &Order> {
+        self.levels.values().flatten()
+    }
+
+    /// Highest price first, older orders still before newer ones on the same price
+    pub fn descending(&self) -> impl Iterator<Item = &Order> {
+        self.levels.values().rev().flatten()
+    }
+
+    #[must_use]
+    pub fn get(&self, id: OrderId) -> Option<&Order> {
+        let price = self.prices.get(&id)?;
+        self.levels.get(price)?.iter().find(|o| o.id == id)
+    }
+
+    pub fn remove(&mut self, id: OrderId) -> Option<Order> {
+        let price = self.prices.remove(&id)?;
+        let level = self.levels.get_mut(&price)?;
+        let index = level.iter().position(|o| o.id == id)?;
+        let order = level.remove(index);
+        if level.is_empty() {
+            self.levels.remove(&price);
+        }
+        order
+    }
+
+    /// Replaces the order with the same id in place, keeping its priority.
+    /// Only valid when neither price nor date changed.
+    pub fn replace(&mut self, order: Order) {
+        if let Some(existing) = self
+            .levels
+            .get_mut(&order.paperclips)
+            .and_then(|level| level.iter_mut().find(|o| o.id == order.id))
+        {
+            *existing = order;
+        }
+    }
+
+    pub fn retain<F: FnMut(&Order) -> bool>(&mut self, mut keep: F) {
+        let prices = &mut self.prices;
+        for level in self.levels.values_mut() {
+            level.retain(|o| {
+                let keep = keep(o);
+                if !keep {
+                    prices.remove(&o.id);
+                }
+                keep
+            });
+        }
+        self.levels.retain(|_, level| !level.is_empty());
+    }
+
+    /// Price levels with the highest price first.
+    /// Only amounts may be changed as ids and prices are indexed.
+    pub fn levels_descending_mut(&mut self) -> impl Iterator<Item = (&u64, &mut VecDeque<Order>)> {
+        self.levels.iter_mut().rev()
+    }
+
+    /// Price levels within the given prices, lowest price first.
+    /// Only amounts may be changed as ids and prices are indexed.
+    pub fn levels_between_mut(
+        &mut self,
+        min_price: u64,
+        max_price: u64,
+    ) -> impl Iterator<Item = (&u64, &mut VecDeque<Order>)> {
+        self.levels.range_mut(min_price..=max_price)
+    }
+
+    /// Removes orders which are not valid anymore like fulfilled ones
+    pub fn cleanup(&mut self) {
+        self.retain(Order::is_valid);
+    }
+
+    /// Same as [`Self::cleanup`] but only looks at the given price levels
+    pub fn cleanup_levels<I: IntoIterator<Item = u64>>(&mut self, prices: I) {
+        for price in prices {
+            if let Some(level) = self.levels.get_mut(&price) {
+                level.retain(|o| {
+                    let valid = o.is_valid();
+                    if !valid {
+                        self.prices.remove(&o.id);
+                    }
+                    valid
+                });
+                if level.is_empty() {
+                    self.levels.remove(&price);
+                }
+            }
+        }
+    }
+}
+
+impl FromIterator<Order> for Levels {
+    fn from_iter<T: IntoIterator<Item = Order>>(iter: T) -> Self {
+        let mut levels = Self::default();
+        for order in iter {
+            levels.insert(order);
+        }
+        levels
+    }
+}
+
+/// Serialized as a plain list of orders like the market always was
+impl Serialize for Levels {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.ascending())
+    }
+}
+
+impl<'de> Deserialize<'de> for Levels {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let orders = Vec::<Order>::deserialize(deserializer)?;
+        Ok(orders.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "ts-rs")]
+impl ts_rs::TS for Levels {
+    fn name() -> String {
+        Vec::<Order>::name_with_type_args(vec![Order::name()])
+    }
+
+    fn inline() -> String {
+        Vec::<Order>::inline()
+    }
+
+    fn dependencies() -> Vec<ts_rs::Dependency> {
+        Vec::<Order>::dependencies()
+    }
+
+    fn transparent() -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Levels {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        Vec::<Order>::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        Vec::<Order>::json_schema(gen)
+    }
+}
+
+#[test]
+fn same_price_keeps_time_priority() {
+    let levels = [Order::example_a(), Order::example_b(), Order::example_c()]
+        .into_iter()
+        .collect::<Levels>();
+    assert_eq!(
+        levels.ascending().copied().collect::<Vec<_>>(),
+        [Order::example_c(), Order::example_b(), Order::example_a()]
+    );
+    assert_eq!(
+        levels.descending().copied().collect::<Vec<_>>(),
+        [Order::example_b(), Order::example_a(), Order::example_c()]
+    );
+}
+
+#[test]
+fn remove_drops_empty_levels() {
+    let mut a = Order::example_a();
+    a.id = 1;
+    let mut c = Order::example_c();
+    c.id = 2;
+    let mut levels = [a, c].into_iter().collect::<Levels>();
+    assert_eq!(levels.remove(3), None);
+    assert_eq!(levels.remove(2), Some(c));
+    assert_eq!(levels, [a].into_iter().collect::<Levels>());
+    assert_eq!(levels.remove(1), Some(a));
+    assert!(levels.is_empty());
+}
+
+#[test]
+fn index_follows_changes() {
+    let mut a = Order::example_a();
+    a.id = 1;
+    let mut b = Order::example_b();
+    b.id = 2;
+    let mut c = Order::example_c();
+    c.id = 3;
+    let mut levels = [a, b, c, Order::example_a()]
+        .into_iter()
+        .collect::<Levels>();
+    assert_eq!(levels.get(2), Some(&b));
+    assert_eq!(levels.get(0), None);
+
+    levels.retain(|o| o.id != 2);
+    assert_eq!(levels.get(2), None);
+    assert_eq!(levels.remove(2), None);
+
+    for (_, level) in levels.levels_between_mut(0, 100) {
+        for order in level {
+            order.amount = 0;
+        }
+    }
+    levels.cleanup_levels([42]);
+    assert_eq!(levels.get(3), None);
+    assert_eq!(levels.get(1), Some(&a));
+    assert_eq!(levels.prices.len(), 1);
+}
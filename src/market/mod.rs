@@ -1,102 +1,366 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+mod error;
+pub mod escrow;
+mod fees;
+mod items;
+mod levels;
 mod order;
 mod trade;
 
-pub use order::{Order, Trader};
-pub use trade::Trade;
+use levels::Levels;
+
+pub use error::Error as MarketError;
+pub use fees::{FeeConfig, FeeRates, MAX_RATE};
+pub use items::Market;
+pub use order::{Escrow, Order, OrderId, Side, Trader};
+pub use trade::{PricingRule, Trade};
 
-/// Keeps all orders for a market of a single item
+/// Keeps all orders for a market of a single item.
+///
+/// Orders are grouped in price levels so placing an order and finding the best ones does not need to look at all the other orders.
+/// Serializes into two plain lists of orders.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ItemMarket {
     /// Buy orders of buyers wanting to buy items.
-    pub buy: Vec<Order>,
+    buy: Levels,
     /// Sell orders of sellers wanting to sell items.
-    pub sell: Vec<Order>,
+    sell: Levels,
 }
 
 impl ItemMarket {
-    /// Sorts orders.
-    /// The first one is the best one for the customer.
+    #[must_use]
+    pub fn new<B, S>(buy: B, sell: S) -> Self
+    where
+        B: IntoIterator<Item = Order>,
+        S: IntoIterator<Item = Order>,
+    {
+        Self {
+            buy: buy.into_iter().collect(),
+            sell: sell.into_iter().collect(),
+        }
+    }
+
+    fn orders_mut(&mut self, side: Side) -> &mut Levels {
+        match side {
+            Side::Buy => &mut self.buy,
+            Side::Sell => &mut self.sell,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buy.is_empty() && self.sell.is_empty()
+    }
+
+    /// Adds the order to the market.
+    /// It will be handled after the orders with a better or the same price which are older.
+    pub fn insert(&mut self, side: Side, order: Order) {
+        self.orders_mut(side).insert(order);
+    }
+
+    /// Buy orders with the best one for the seller first.
     ///
     /// Orders are first sorted by price, then by date.
     /// Older orders are always sorted first.
-    pub fn sort(&mut self) {
-        self.buy.sort_by_key(|o| o.date);
-        self.buy.sort_by(|a, b| b.paperclips.cmp(&a.paperclips));
+    pub fn buy_orders(&self) -> impl Iterator<Item = &Order> {
+        self.buy.descending()
+    }
 
-        self.sell.sort_by_key(|o| o.date);
-        self.sell.sort_by(|a, b| a.paperclips.cmp(&b.paperclips));
+    /// Sell orders with the best one for the buyer first.
+    ///
+    /// Orders are first sorted by price, then by date.
+    /// Older orders are always sorted first.
+    pub fn sell_orders(&self) -> impl Iterator<Item = &Order> {
+        self.sell.ascending()
     }
 
     /// Removes orders with amount 0
     pub fn cleanup(&mut self) {
-        self.buy = self.buy.iter().filter(|o| o.is_valid()).copied().collect();
-        self.sell = self.sell.iter().filter(|o| o.is_valid()).copied().collect();
+        self.buy.cleanup();
+        self.sell.cleanup();
+    }
+
+    /// Finds the order with the given id
+    #[must_use]
+    pub fn get(&self, id: OrderId) -> Option<(Side, &Order)> {
+        self.buy
+            .get(id)
+            .map(|o| (Side::Buy, o))
+            .or_else(|| self.sell.get(id).map(|o| (Side::Sell, o)))
+    }
+
+    fn issued(&self, trader: Trader, id: OrderId) -> Result<(Side, Order), MarketError> {
+        // Orders from before ids existed all share the id 0 and can not be told apart
+        if id == 0 {
+            return Err(MarketError::UnknownOrder(id));
+        }
+        let (side, order) = self.get(id).ok_or(MarketError::UnknownOrder(id))?;
+        if order.trader != trader {
+            return Err(MarketError::NotIssuer { order: id, trader });
+        }
+        Ok((side, *order))
+    }
+
+    /// Removes the order when it was issued by the trader.
+    ///
+    /// Returns the removed order so its `Order::escrow` can be given back.
+    /// # Errors
+    /// Errors when the order does not exist or was issued by someone else
+    pub fn cancel(&mut self, trader: Trader, id: OrderId) -> Result<(Side, Order), MarketError> {
+        let (side, _) = self.issued(trader, id)?;
+        let order = self
+            .orders_mut(side)
+            .remove(id)
+            .ok_or(MarketError::UnknownOrder(id))?;
+        Ok((side, order))
+    }
+
+    /// Changes amount and price of an order issued by the trader.
+    ///
+    /// Changing the price or increasing the amount puts the order behind the other orders with the same price.
+    /// Returns the order before the change in order to adapt the escrow.
+    /// # Errors
+    /// Errors when the order does not exist, was issued by someone else or the change is not valid
+    pub fn modify(
+        &mut self,
+        trader: Trader,
+        id: OrderId,
+        amount: u32,
+        paperclips: u64,
+    ) -> Result<(Side, Order), MarketError> {
+        let (side, before) = self.issued(trader, id)?;
+        let mut changed = Order {
+            amount,
+            paperclips,
+            ..before
+        };
+        if !changed.is_valid() {
+            return Err(MarketError::InvalidOrder);
+        }
+        let orders = self.orders_mut(side);
+        if paperclips != before.paperclips || amount > before.amount {
+            changed.date = Utc::now();
+            orders.remove(id);
+            orders.insert(changed);
+        } else {
+            orders.replace(changed);
+        }
+        Ok((side, before))
+    }
+
+    /// Removes orders which expired until `now`.
+    ///
+    /// Should be done before [`Self::resolve`] as expired orders are still resolved otherwise.
+    /// Returns the removed orders so their owners can be notified and get their `Order::escrow` back.
+    pub fn remove_expired(&mut self, now: DateTime<Utc>) -> Vec<(Side, Order)> {
+        let mut expired = Vec::new();
+        for side in [Side::Buy, Side::Sell] {
+            self.orders_mut(side).retain(|order| {
+                if order.is_expired(now) {
+                    expired.push((side, *order));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        expired
     }
 
+    /// Matches buy and sell orders with each other.
+    ///
+    /// Only the price levels where the buyer pays at least what the seller wants are looked at.
+    /// Orders of different locations never trade with each other.
+    /// Trades use the price of the [`PricingRule::default`].
     pub fn resolve(&mut self) -> Vec<Trade> {
-        self.sort();
+        self.resolve_with(PricingRule::default())
+    }
+
+    /// Same as [`Self::resolve`] but trades use the price of the given [`PricingRule`].
+    pub fn resolve_with(&mut self, pricing: PricingRule) -> Vec<Trade> {
         let mut trades = Vec::new();
-        for buy in &mut self.buy {
-            for sell in &mut self.sell {
-                // Early abort for performance
-                if buy.amount == 0 || buy.paperclips < sell.paperclips {
-                    break;
+        // Sell levels below are fully drained and dont need to be looked at again
+        let Some(mut sell_from) = self.sell.lowest_price() else {
+            return trades;
+        };
+        let mut buy_prices = Vec::new();
+        let mut sell_prices = BTreeSet::new();
+        'levels: for (buy_price, buy_level) in self.buy.levels_descending_mut() {
+            buy_prices.push(*buy_price);
+            for buy in buy_level {
+                if *buy_price < sell_from {
+                    break 'levels;
                 }
+                let mut drained = true;
+                for (sell_price, sell_level) in self.sell.levels_between_mut(sell_from, *buy_price)
+                {
+                    sell_prices.insert(*sell_price);
+                    for sell in sell_level.iter_mut() {
+                        // Early abort for performance
+                        if buy.amount == 0 {
+                            break;
+                        }
 
-                if let Some((trade, remaining_buy, remaining_sell)) = Trade::resolve(*buy, *sell) {
-                    *buy = remaining_buy;
-                    *sell = remaining_sell;
-                    trades.push(trade);
+                        if let Some((trade, remaining_buy, remaining_sell)) =
+                            Trade::resolve_with(pricing, *buy, *sell)
+                        {
+                            *buy = remaining_buy;
+                            *sell = remaining_sell;
+                            trades.push(trade);
+                        }
+                    }
+                    // The next buy order does not need to skip them again
+                    while sell_level.front().is_some_and(|o| o.amount == 0) {
+                        sell_level.pop_front();
+                    }
+                    drained &= sell_level.is_empty();
+                    if drained {
+                        sell_from = sell_price.saturating_add(1);
+                    }
+                    if buy.amount == 0 {
+                        break;
+                    }
                 }
             }
         }
-        self.cleanup();
+        self.buy.cleanup_levels(buy_prices);
+        self.sell.cleanup_levels(sell_prices);
         trades
     }
 }
 
 #[test]
 fn can_serde_parse_market() {
-    let data = ItemMarket {
-        buy: vec![Order::example_b(), Order::example_a()],
-        sell: vec![Order::example_b()],
-    };
+    let data = ItemMarket::new(
+        vec![Order::example_b(), Order::example_a()],
+        vec![Order::example_b()],
+    );
     crate::test_helper::can_serde_parse(&data);
 }
 
 #[test]
 fn can_sort_buy() {
-    let mut orders = ItemMarket {
-        buy: vec![Order::example_a(), Order::example_b(), Order::example_c()],
-        sell: vec![],
-    };
+    let orders = ItemMarket::new(
+        vec![Order::example_a(), Order::example_b(), Order::example_c()],
+        vec![],
+    );
     let expected = vec![Order::example_b(), Order::example_a(), Order::example_c()];
     dbg!(&expected);
 
-    orders.sort();
-    assert_eq!(orders.sell, vec![]);
-    dbg!(&orders.buy);
-    assert_eq!(orders.buy, expected);
+    assert_eq!(orders.sell_orders().count(), 0);
+    let buy = orders.buy_orders().copied().collect::<Vec<_>>();
+    dbg!(&buy);
+    assert_eq!(buy, expected);
 }
 
 #[test]
 fn can_sort_sell() {
-    let mut orders = ItemMarket {
-        buy: vec![],
-        sell: vec![Order::example_a(), Order::example_b(), Order::example_c()],
-    };
+    let orders = ItemMarket::new(
+        vec![],
+        vec![Order::example_a(), Order::example_b(), Order::example_c()],
+    );
     let expected = vec![Order::example_c(), Order::example_b(), Order::example_a()];
     dbg!(&expected);
 
-    orders.sort();
-    assert_eq!(orders.buy, vec![]);
-    dbg!(&orders.sell);
-    assert_eq!(orders.sell, expected);
+    assert_eq!(orders.buy_orders().count(), 0);
+    let sell = orders.sell_orders().copied().collect::<Vec<_>>();
+    dbg!(&sell);
+    assert_eq!(sell, expected);
+}
+
+#[test]
+fn resolve_uses_price_of_resting_order() {
+    let resting = Order::example_b();
+    let incoming = Order::example_c().reduce_to(10);
+    let mut market = ItemMarket::new(vec![resting], vec![incoming]);
+    assert_eq!(market.resolve(), [Trade::new_test(10, 666)]);
+
+    let mut market = ItemMarket::new(vec![resting], vec![incoming]);
+    assert_eq!(
+        market.resolve_with(PricingRule::Cheaper),
+        [Trade {
+            buyer_refund: 6240,
+            ..Trade::new_test(10, 42)
+        }]
+    );
+}
+
+#[test]
+fn can_parse_previous_format() {
+    let json = serde_json::json!({
+        "buy": [Order::example_a(), Order::example_b()],
+        "sell": [Order::example_c()],
+    });
+    let market: ItemMarket = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        market.buy_orders().copied().collect::<Vec<_>>(),
+        [Order::example_b(), Order::example_a()]
+    );
+    assert_eq!(
+        market.sell_orders().copied().collect::<Vec<_>>(),
+        [Order::example_c()]
+    );
+}
+
+#[test]
+fn insert_keeps_time_priority() {
+    let mut market = ItemMarket::default();
+    market.insert(Side::Sell, Order::example_a());
+    market.insert(Side::Sell, Order::example_c());
+    market.insert(Side::Sell, Order::example_b());
+    assert_eq!(
+        market.sell_orders().copied().collect::<Vec<_>>(),
+        [Order::example_c(), Order::example_b(), Order::example_a()]
+    );
+    assert!(!market.is_empty());
+}
+
+#[test]
+fn resolve_over_multiple_price_levels() {
+    let mut market = ItemMarket::new(
+        vec![
+            Order::new_test_simple(10, 50),
+            Order::new_test_simple(5, 30),
+        ],
+        vec![
+            Order::new_test_simple(4, 20),
+            Order::new_test_simple(4, 40),
+            Order::new_test_simple(10, 45),
+        ],
+    );
+    let trades = market.resolve();
+    assert_eq!(
+        trades,
+        [
+            Trade {
+                buyer_refund: 120,
+                ..Trade::new_test(4, 20)
+            },
+            Trade {
+                buyer_refund: 40,
+                ..Trade::new_test(4, 40)
+            },
+            Trade {
+                buyer_refund: 10,
+                ..Trade::new_test(2, 45)
+            },
+        ]
+    );
+    assert_eq!(
+        market,
+        ItemMarket::new(
+            vec![Order::new_test_simple(5, 30)],
+            vec![Order::new_test_simple(8, 45)]
+        )
+    );
 }
 
 #[test]
@@ -108,10 +372,10 @@ fn resolve_does_nothing_when_both_empty() {
 
 #[test]
 fn resolve_does_nothing_when_buy_empty() {
-    let mut orders = ItemMarket {
-        buy: vec![],
-        sell: vec![Order::example_a(), Order::example_b(), Order::example_c()],
-    };
+    let mut orders = ItemMarket::new(
+        vec![],
+        vec![Order::example_a(), Order::example_b(), Order::example_c()],
+    );
     let trades = orders.resolve();
     assert_eq!(trades, vec![]);
 }
@@ -123,14 +387,8 @@ fn resolve_does_nothing_when_different_solarsystem() {
     buy.solarsystem = Solarsystem::Arama;
     let mut sell = Order::example_b();
     sell.solarsystem = Solarsystem::Iramil;
-    let expected = ItemMarket {
-        buy: vec![buy],
-        sell: vec![sell],
-    };
-    let mut orders = ItemMarket {
-        buy: vec![buy],
-        sell: vec![sell],
-    };
+    let expected = ItemMarket::new(vec![buy], vec![sell]);
+    let mut orders = ItemMarket::new(vec![buy], vec![sell]);
     let trades = orders.resolve();
     assert_eq!(trades, vec![]);
     assert_eq!(orders, expected);
@@ -138,10 +396,7 @@ fn resolve_does_nothing_when_different_solarsystem() {
 
 #[test]
 fn resolve_exact_single() {
-    let mut orders = ItemMarket {
-        buy: vec![Order::example_b()],
-        sell: vec![Order::example_b()],
-    };
+    let mut orders = ItemMarket::new(vec![Order::example_b()], vec![Order::example_b()]);
     let trades = orders.resolve();
     assert_eq!(trades, vec![Trade::new_test(1337, 666)]);
     assert_eq!(orders, ItemMarket::default());
@@ -149,87 +404,169 @@ fn resolve_exact_single() {
 
 #[test]
 fn resolve_exact_with_remaining_sell_orders() {
-    let mut orders = ItemMarket {
-        buy: vec![Order::example_b()],
-        sell: vec![Order::example_b(), Order::example_a()],
-    };
+    let mut orders = ItemMarket::new(
+        vec![Order::example_b()],
+        vec![Order::example_b(), Order::example_a()],
+    );
     let trades = orders.resolve();
     dbg!(&trades);
     assert_eq!(trades.len(), 1);
-    assert_eq!(
-        orders,
-        ItemMarket {
-            buy: vec![],
-            sell: vec![Order::example_a()],
-        }
-    );
+    assert_eq!(orders, ItemMarket::new(vec![], vec![Order::example_a()]));
 }
 
 #[test]
 fn resolve_exact_with_remaining_buy_orders() {
-    let mut orders = ItemMarket {
-        buy: vec![Order::example_b(), Order::example_a()],
-        sell: vec![Order::example_b()],
-    };
+    let mut orders = ItemMarket::new(
+        vec![Order::example_b(), Order::example_a()],
+        vec![Order::example_b()],
+    );
     let trades = orders.resolve();
     dbg!(&trades);
     assert_eq!(trades.len(), 1);
-    assert_eq!(
-        orders,
-        ItemMarket {
-            buy: vec![Order::example_a()],
-            sell: vec![],
-        }
-    );
+    assert_eq!(orders, ItemMarket::new(vec![Order::example_a()], vec![]));
 }
 
 #[test]
 fn resolve_partial() {
-    let mut orders = ItemMarket {
-        buy: vec![Order::example_a()],
-        sell: vec![Order::example_b()],
-    };
+    let mut orders = ItemMarket::new(vec![Order::example_a()], vec![Order::example_b()]);
     let trades = orders.resolve();
     assert_eq!(trades, vec![Trade::new_test(42, 666)]);
     assert_eq!(
         orders,
-        ItemMarket {
-            buy: vec![],
-            sell: vec![Order::example_b().reduce_to(1295)],
-        }
+        ItemMarket::new(vec![], vec![Order::example_b().reduce_to(1295)])
     );
 }
 
 #[test]
 fn resolve_exact_with_better_wrong_locations() {
     use crate::fixed::solarsystem::Solarsystem;
-    let mut orders = ItemMarket {
-        buy: vec![
+    let mut orders = ItemMarket::new(
+        vec![
             Order::new_test("1997-12-19T16:53:14Z", Solarsystem::Arama, 1, 1337),
             Order::new_test("1997-12-19T16:53:14Z", Solarsystem::default(), 20, 5),
         ],
-        sell: vec![
+        vec![
             Order::new_test("1997-12-19T16:53:14Z", Solarsystem::Iramil, 1337, 1),
             Order::new_test("1997-12-19T16:53:14Z", Solarsystem::default(), 20, 5),
         ],
-    };
+    );
     let trades = orders.resolve();
     assert_eq!(trades, vec![Trade::new_test(20, 5)]);
     assert_eq!(
         orders,
-        ItemMarket {
-            buy: vec![Order::new_test(
+        ItemMarket::new(
+            vec![Order::new_test(
                 "1997-12-19T16:53:14Z",
                 Solarsystem::Arama,
                 1,
                 1337
             ),],
-            sell: vec![Order::new_test(
+            vec![Order::new_test(
                 "1997-12-19T16:53:14Z",
                 Solarsystem::Iramil,
                 1337,
                 1
-            ),],
-        }
+            ),]
+        )
+    );
+}
+
+#[cfg(test)]
+fn example_with_ids() -> ItemMarket {
+    let mut buy = Order::example_a();
+    buy.id = 1;
+    let mut sell = Order::example_c();
+    sell.id = 2;
+    sell.trader = Trader::Player(crate::player::Player::Telegram(666));
+    ItemMarket::new(vec![buy], vec![sell])
+}
+
+#[test]
+fn cancel_only_by_issuer() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let player = Trader::Player(crate::player::Player::Telegram(666));
+    let mut market = example_with_ids();
+    assert_eq!(
+        market.cancel(NpcFaction::Guards.into(), 2),
+        Err(MarketError::NotIssuer {
+            order: 2,
+            trader: NpcFaction::Guards.into()
+        })
+    );
+    assert_eq!(market.cancel(player, 3), Err(MarketError::UnknownOrder(3)));
+
+    let (side, order) = market.cancel(player, 2).unwrap();
+    assert_eq!(side, Side::Sell);
+    assert_eq!(order.escrow(side), Escrow::Items(666));
+    assert_eq!(market.sell_orders().count(), 0);
+    assert_eq!(market.buy_orders().count(), 1);
+}
+
+#[test]
+fn cancel_and_modify_reject_orders_without_id() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let guards = NpcFaction::Guards.into();
+    let mut market = ItemMarket::new(vec![Order::example_a()], vec![]);
+    assert_eq!(market.cancel(guards, 0), Err(MarketError::UnknownOrder(0)));
+    assert_eq!(
+        market.modify(guards, 0, 1, 1),
+        Err(MarketError::UnknownOrder(0))
+    );
+    assert_eq!(market.buy_orders().count(), 1);
+}
+
+#[test]
+fn modify_keeps_priority_when_reducing() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let mut market = example_with_ids();
+    let (side, before) = market
+        .modify(NpcFaction::Guards.into(), 1, 10, 666)
+        .unwrap();
+    assert_eq!(side, Side::Buy);
+    assert_eq!(Some((Side::Buy, &before)), example_with_ids().get(1));
+    assert_eq!(market.get(1), Some((Side::Buy, &before.reduce_to(10))));
+}
+
+#[test]
+fn modify_price_loses_priority() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let mut market = example_with_ids();
+    market
+        .modify(NpcFaction::Guards.into(), 1, 42, 700)
+        .unwrap();
+    let (_, order) = market.get(1).unwrap();
+    assert_eq!(order.paperclips, 700);
+    assert!(order.date > example_with_ids().get(1).unwrap().1.date);
+}
+
+#[test]
+fn modify_rejects_invalid() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let mut market = example_with_ids();
+    assert_eq!(
+        market.modify(NpcFaction::Guards.into(), 1, 0, 700),
+        Err(MarketError::InvalidOrder)
+    );
+    assert_eq!(market, example_with_ids());
+}
+
+#[test]
+fn removes_expired_orders() {
+    use crate::fixed::npc_faction::NpcFaction;
+    let player = Trader::Player(crate::player::Player::Telegram(666));
+    let now = Utc::now();
+    let (_, buy) = example_with_ids()
+        .cancel(NpcFaction::Guards.into(), 1)
+        .unwrap();
+    let (_, sell) = example_with_ids().cancel(player, 2).unwrap();
+    let mut market = ItemMarket::new(
+        vec![buy.with_expiry(now)],
+        vec![sell.with_expiry(now + chrono::Duration::hours(1))],
     );
+    let expired = market.remove_expired(now);
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].0, Side::Buy);
+    assert_eq!(expired[0].1.id, 1);
+    assert_eq!(market.buy_orders().count(), 0);
+    assert_eq!(market.sell_orders().count(), 1);
 }
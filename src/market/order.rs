@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -7,16 +9,46 @@ use crate::player::Player;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum Trader {
     Npc(NpcFaction),
     Player(Player),
 }
 
+/// Identifies an order. Unique across all markets.
+pub type OrderId = u64;
+
+/// Which side of the market an order is on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// What is reserved while an order is within the market
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escrow {
+    /// Buy orders reserve the paperclips to pay for the items
+    Paperclips(u64),
+    /// Sell orders reserve the items to be sold
+    Items(u32),
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
+    /// Stays the same for the whole lifetime of the order.
+    /// Orders from before ids existed have the id 0.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts-rs", ts(type = "number"))]
+    pub id: OrderId,
+
     /// Time when the order was placed. Older orders with the same price are always handled first.
     #[cfg_attr(
         feature = "ts-rs",
@@ -24,6 +56,14 @@ pub struct Order {
     )]
     pub date: DateTime<Utc>,
 
+    /// The order is removed from the market after this time. Stays until fulfilled when not set.
+    #[cfg_attr(
+        feature = "ts-rs",
+        ts(type = "`${number}-${number}-${number}T${number}:${number}:${number}${string}Z`")
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+
     /// Identifies the location of the order (together with the station index).
     pub solarsystem: Solarsystem,
     /// Identifies the location of the order (together with the solarsystem).
@@ -41,6 +81,20 @@ pub struct Order {
     pub paperclips: u64,
 }
 
+/// Ids are based on the time in microseconds to stay unique across restarts.
+/// They stay below 2^53 for centuries so JavaScript numbers can represent them exactly.
+/// The atomic ensures unique ids for orders created within the same microsecond.
+fn next_id(now: DateTime<Utc>) -> OrderId {
+    static LAST: AtomicU64 = AtomicU64::new(0);
+    let candidate = now.timestamp_micros().unsigned_abs();
+    let previous = LAST
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(candidate.max(last + 1))
+        })
+        .unwrap_or_else(|previous| previous);
+    candidate.max(previous + 1)
+}
+
 impl Order {
     #[must_use]
     pub fn new_now(
@@ -50,8 +104,11 @@ impl Order {
         amount: u32,
         paperclips: u64,
     ) -> Self {
+        let now = Utc::now();
         Self {
-            date: Utc::now(),
+            id: next_id(now),
+            date: now,
+            expires: None,
             solarsystem,
             station,
             trader,
@@ -69,7 +126,9 @@ impl Order {
         paperclips: u64,
     ) -> Self {
         Self {
+            id: 0,
             date: DateTime::parse_from_rfc3339(date).unwrap().into(),
+            expires: None,
             solarsystem,
             station: 0,
             trader: Trader::Npc(NpcFaction::Guards),
@@ -89,6 +148,29 @@ impl Order {
         )
     }
 
+    /// Good till date order which is removed from the market after the given time
+    #[must_use]
+    pub const fn with_expiry(self, expires: DateTime<Utc>) -> Self {
+        Self {
+            expires: Some(expires),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// What has to be reserved for this order and given back when it is removed unfulfilled
+    #[must_use]
+    pub const fn escrow(&self, side: Side) -> Escrow {
+        match side {
+            Side::Buy => Escrow::Paperclips(self.total_paperclips()),
+            Side::Sell => Escrow::Items(self.amount),
+        }
+    }
+
     #[must_use]
     pub const fn reduce_to(&self, remaining: u32) -> Self {
         Self {
@@ -167,9 +249,15 @@ fn can_serde_parse_trader_player() {
 #[test]
 fn can_serde_parse_order() {
     let data = Order {
+        id: 42,
         date: DateTime::parse_from_rfc3339("1996-12-19T16:57:12.421337Z")
             .unwrap()
             .into(),
+        expires: Some(
+            DateTime::parse_from_rfc3339("1996-12-20T16:57:12Z")
+                .unwrap()
+                .into(),
+        ),
         solarsystem: Solarsystem::default(),
         station: 42,
         trader: Trader::Npc(NpcFaction::Guards),
@@ -178,3 +266,39 @@ fn can_serde_parse_order() {
     };
     crate::test_helper::can_serde_parse(&data);
 }
+
+#[test]
+fn can_parse_order_without_id_and_expiry() {
+    let json = r#"{"date":"1996-12-19T16:57:12Z","solarsystem":"Wabinihwa","station":0,"trader":"guards","amount":5,"paperclips":42}"#;
+    let order = serde_json::from_str::<Order>(json).unwrap();
+    assert_eq!(order.id, 0);
+    assert_eq!(order.expires, None);
+    assert_eq!((order.amount, order.paperclips), (5, 42));
+}
+
+#[test]
+fn new_orders_have_unique_ids() {
+    let a = Order::new_now(Solarsystem::default(), 0, NpcFaction::Guards.into(), 1, 1);
+    let b = Order::new_now(Solarsystem::default(), 0, NpcFaction::Guards.into(), 1, 1);
+    assert_ne!(a.id, 0);
+    assert!(b.id > a.id);
+    // Number.MAX_SAFE_INTEGER in JavaScript
+    assert!(b.id < 1 << 53);
+}
+
+#[test]
+fn order_expires() {
+    let now = Utc::now();
+    let order = Order::new_test_simple(1, 1);
+    assert!(!order.is_expired(now));
+    let order = order.with_expiry(now);
+    assert!(order.is_expired(now));
+    assert!(!order.is_expired(now - chrono::Duration::seconds(1)));
+}
+
+#[test]
+fn escrow_depends_on_side() {
+    let order = Order::new_test_simple(5, 42);
+    assert_eq!(order.escrow(Side::Buy), Escrow::Paperclips(210));
+    assert_eq!(order.escrow(Side::Sell), Escrow::Items(5));
+}
@@ -4,9 +4,33 @@ use crate::fixed::solarsystem::Solarsystem;
 
 use super::{Order, Trader};
 
+/// Which price is used when a buy order pays more than a sell order asks for
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PricingRule {
+    /// Price of the order which was in the market first.
+    /// The newer order accepted that price when it was placed.
+    /// The sell price is used when both were placed at the same time.
+    #[default]
+    RestingOrder,
+    /// Always the lower price which is the sell price
+    Cheaper,
+}
+
+impl PricingRule {
+    #[must_use]
+    pub fn price(self, buy_order: &Order, sell_order: &Order) -> u64 {
+        match self {
+            Self::RestingOrder if buy_order.date < sell_order.date => buy_order.paperclips,
+            Self::RestingOrder | Self::Cheaper => buy_order.paperclips.min(sell_order.paperclips),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
-#[serde(rename = "Trade")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", rename = "Trade")]
 pub struct Trade {
     pub solarsystem: Solarsystem,
     pub station: u8,
@@ -18,6 +42,16 @@ pub struct Trade {
     /// Paperclips per item.
     /// Total Price is calculated by `amount` * `paperclips`.
     pub paperclips: u64,
+
+    /// Paperclips the seller pays on the total price.
+    /// See `FeeRates::apply_sales_tax`.
+    #[serde(default)]
+    pub sales_tax: u64,
+
+    /// Paperclips the buyer reserved but did not need as the trade happened below the price of the buy order.
+    /// See `escrow::reserve`.
+    #[serde(default)]
+    pub buyer_refund: u64,
 }
 
 impl Trade {
@@ -32,11 +66,23 @@ impl Trade {
             seller: Trader::Npc(NpcFaction::Guards),
             amount,
             paperclips,
+            sales_tax: 0,
+            buyer_refund: 0,
         }
     }
 
+    /// Resolves with the [`PricingRule::default`]
     #[must_use]
     pub fn resolve(buy: Order, sell: Order) -> Option<(Self, Order, Order)> {
+        Self::resolve_with(PricingRule::default(), buy, sell)
+    }
+
+    #[must_use]
+    pub fn resolve_with(
+        pricing: PricingRule,
+        buy: Order,
+        sell: Order,
+    ) -> Option<(Self, Order, Order)> {
         if buy.solarsystem != sell.solarsystem
             || buy.station != sell.station
             || buy.paperclips < sell.paperclips
@@ -46,24 +92,34 @@ impl Trade {
             return None;
         }
         let amount = buy.amount.min(sell.amount);
+        let paperclips = pricing.price(&buy, &sell);
         let trade = Self {
             solarsystem: sell.solarsystem,
             station: sell.station,
             buyer: buy.trader,
             seller: sell.trader,
             amount,
-            paperclips: buy.paperclips.min(sell.paperclips),
+            paperclips,
+            sales_tax: 0,
+            buyer_refund: (buy.paperclips - paperclips).saturating_mul(u64::from(amount)),
         };
         let remaining_buy = buy.reduce_by(amount);
         let remaining_sell = sell.reduce_by(amount);
         Some((trade, remaining_buy, remaining_sell))
     }
 
+    /// Gross paperclips the buyer pays
     #[must_use]
     pub const fn total_paperclips(&self) -> u64 {
         let amount = self.amount as u64;
         self.paperclips.saturating_mul(amount)
     }
+
+    /// Paperclips the seller gets after the sales tax
+    #[must_use]
+    pub const fn net_paperclips(&self) -> u64 {
+        self.total_paperclips().saturating_sub(self.sales_tax)
+    }
 }
 
 #[test]
@@ -72,6 +128,14 @@ fn can_serde_parse_trade() {
     crate::test_helper::can_serde_parse(&data);
 }
 
+#[test]
+fn can_parse_trade_without_sales_tax() {
+    let json = r#"{"solarsystem":"Wabinihwa","station":0,"buyer":"guards","seller":"guards","amount":42,"paperclips":666}"#;
+    let trade: Trade = serde_json::from_str(json).unwrap();
+    assert_eq!(trade, Trade::new_test(42, 666));
+    assert_eq!(trade.net_paperclips(), trade.total_paperclips());
+}
+
 #[test]
 fn solarsystem_doesnt_trade() {
     let buy = Order::new_test("1997-12-27T16:00:00Z", Solarsystem::Iramil, 5, 666);
@@ -149,11 +213,53 @@ fn trade_uses_cheaper_price() {
     let buy = Order::new_test_simple(5, 30);
     let sell = Order::new_test_simple(5, 10);
     let (trade, remaining_buy, remaining_sell) = Trade::resolve(buy, sell).unwrap();
-    assert_eq!(trade, Trade::new_test(5, 10));
+    assert_eq!(
+        trade,
+        Trade {
+            buyer_refund: 100,
+            ..Trade::new_test(5, 10)
+        }
+    );
     assert_eq!(remaining_buy.amount, 0);
     assert_eq!(remaining_sell.amount, 0);
 }
 
+#[test]
+fn trade_uses_price_of_older_buy() {
+    let buy = Order::new_test("1997-12-19T16:00:00Z", Solarsystem::default(), 5, 30);
+    let sell = Order::new_test("1997-12-19T17:00:00Z", Solarsystem::default(), 5, 10);
+    let (trade, _, _) = Trade::resolve(buy, sell).unwrap();
+    assert_eq!(trade, Trade::new_test(5, 30));
+}
+
+#[test]
+fn trade_uses_price_of_older_sell() {
+    let buy = Order::new_test("1997-12-19T17:00:00Z", Solarsystem::default(), 5, 30);
+    let sell = Order::new_test("1997-12-19T16:00:00Z", Solarsystem::default(), 5, 10);
+    let (trade, _, _) = Trade::resolve(buy, sell).unwrap();
+    assert_eq!(
+        trade,
+        Trade {
+            buyer_refund: 100,
+            ..Trade::new_test(5, 10)
+        }
+    );
+}
+
+#[test]
+fn cheaper_rule_ignores_age() {
+    let buy = Order::new_test("1997-12-19T16:00:00Z", Solarsystem::default(), 5, 30);
+    let sell = Order::new_test("1997-12-19T17:00:00Z", Solarsystem::default(), 5, 10);
+    let (trade, _, _) = Trade::resolve_with(PricingRule::Cheaper, buy, sell).unwrap();
+    assert_eq!(
+        trade,
+        Trade {
+            buyer_refund: 100,
+            ..Trade::new_test(5, 10)
+        }
+    );
+}
+
 #[test]
 fn has_correct_buyer_seller() {
     use crate::fixed::npc_faction::NpcFaction;
@@ -4,6 +4,7 @@ use crate::fixed::solarsystem::Solarsystem;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "PlayerGeneral")]
 pub struct General {
     pub home_solarsystem: Solarsystem,
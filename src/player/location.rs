@@ -7,6 +7,7 @@ use crate::site::Site;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum PlayerLocation {
     Site(PlayerLocationSite),
@@ -16,6 +17,7 @@ pub enum PlayerLocation {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerLocationSite {
     pub solarsystem: Solarsystem,
@@ -24,6 +26,7 @@ pub struct PlayerLocationSite {
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerLocationStation {
     pub solarsystem: Solarsystem,
@@ -32,10 +35,18 @@ pub struct PlayerLocationStation {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerLocationWarp {
     pub solarsystem: Solarsystem,
     pub towards: Site,
+
+    /// Rounds already spent in warp
+    #[serde(default)]
+    pub progress: u8,
+    /// Rounds remaining until arrival at `towards`
+    #[serde(default)]
+    pub eta: u8,
 }
 
 impl Default for PlayerLocation {
@@ -44,6 +55,36 @@ impl Default for PlayerLocation {
     }
 }
 
+impl PlayerLocationWarp {
+    /// Starts a warp which takes the given amount of rounds.
+    /// See `site::warp_rounds`.
+    #[must_use]
+    pub const fn new(solarsystem: Solarsystem, towards: Site, rounds: u8) -> Self {
+        Self {
+            solarsystem,
+            towards,
+            progress: 0,
+            eta: rounds,
+        }
+    }
+
+    #[must_use]
+    pub const fn has_arrived(&self) -> bool {
+        self.eta == 0
+    }
+
+    /// Progresses the warp by one round.
+    /// # Returns
+    /// Returns true when the target has been reached.
+    pub fn advance(&mut self) -> bool {
+        if self.eta > 0 {
+            self.eta -= 1;
+            self.progress = self.progress.saturating_add(1);
+        }
+        self.has_arrived()
+    }
+}
+
 impl PlayerLocation {
     #[must_use]
     pub const fn solarsystem(&self) -> Solarsystem {
@@ -69,10 +110,40 @@ fn can_identify_warp() {
     let data = PlayerLocation::Warp(PlayerLocationWarp {
         solarsystem: Solarsystem::default(),
         towards: Site::Station(42),
+        progress: 1,
+        eta: 2,
     });
     crate::test_helper::can_serde_parse(&data);
 }
 
+#[test]
+fn can_parse_warp_without_progress() -> anyhow::Result<()> {
+    let data = serde_json::from_str::<PlayerLocation>(
+        r#"{"solarsystem":"Arama","towards":{"kind":"station","unique":0}}"#,
+    )?;
+    assert_eq!(
+        data,
+        PlayerLocation::Warp(PlayerLocationWarp::new(
+            Solarsystem::Arama,
+            Site::Station(0),
+            0
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn warp_takes_rounds() {
+    let mut warp = PlayerLocationWarp::new(Solarsystem::default(), Site::Station(0), 2);
+    assert!(!warp.has_arrived());
+    assert!(!warp.advance());
+    assert_eq!((warp.progress, warp.eta), (1, 1));
+    assert!(warp.advance());
+    assert_eq!((warp.progress, warp.eta), (2, 0));
+    assert!(warp.advance());
+    assert_eq!((warp.progress, warp.eta), (2, 0));
+}
+
 #[test]
 fn can_identify_station() {
     let data = PlayerLocation::Station(PlayerLocationStation {
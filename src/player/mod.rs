@@ -12,6 +12,7 @@ pub use station_assets::StationAssets;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", tag = "platform", content = "id")]
 pub enum Player {
     Telegram(i64),
@@ -33,10 +34,10 @@ impl std::str::FromStr for Player {
     }
 }
 
-impl ToString for Player {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Player {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Telegram(id) => format!("telegram-{}", id),
+            Self::Telegram(id) => write!(f, "telegram-{}", id),
         }
     }
 }
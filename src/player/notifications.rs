@@ -1,18 +1,23 @@
 use serde::{Deserialize, Serialize};
 
 use crate::fixed::item::Item;
-use crate::market::Trade;
+use crate::market::{Order, Side, Trade};
 use crate::site;
 
 /// Stuff which happened in a game round.
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "PlayerNotifications")]
 pub struct Notifications {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub site_log: Vec<site::Log>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub trades: Vec<(Item, Trade)>,
+    /// Orders removed from the market as they expired.
+    /// See `market::Market::remove_expired`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expired_orders: Vec<(Item, Side, Order)>,
 }
 
 impl From<Vec<site::Log>> for Notifications {
@@ -37,6 +42,7 @@ impl Notifications {
     pub fn append(&mut self, other: &mut Self) {
         self.site_log.append(&mut other.site_log);
         self.trades.append(&mut other.trades);
+        self.expired_orders.append(&mut other.expired_orders);
     }
 }
 
@@ -45,3 +51,15 @@ fn can_serde_parse_empty() {
     let data = Notifications::default();
     crate::test_helper::can_serde_parse(&data);
 }
+
+#[test]
+fn can_serde_parse_expired_order() {
+    use crate::fixed::npc_faction::NpcFaction;
+    use crate::fixed::solarsystem::Solarsystem;
+    let order = Order::new_now(Solarsystem::default(), 0, NpcFaction::Guards.into(), 1, 42);
+    let data = Notifications {
+        expired_orders: vec![(Item::EXAMPLE, Side::Sell, order)],
+        ..Notifications::default()
+    };
+    crate::test_helper::can_serde_parse(&data);
+}
@@ -6,6 +6,7 @@ use crate::storage::Storage;
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "PlayerStationAssets")]
 pub struct StationAssets {
     /// When the player is docked this ship is the ship the player sits in.
@@ -65,7 +66,8 @@ fn switch_ship_works_from_none() {
     let ship = Ship::new(
         &crate::fixed::Statics::default(),
         crate::fixed::shiplayout::ShipLayout::Hecate.into(),
-    );
+    )
+    .unwrap();
     let mut assets = StationAssets {
         current_ship: None,
         ships: vec![ship.clone()],
@@ -82,11 +84,13 @@ fn switch_ship_works_from_some() {
     let ship_a = Ship::new(
         &statics,
         crate::fixed::shiplayout::ShipLayout::Hecate.into(),
-    );
+    )
+    .unwrap();
     let ship_b = Ship::new(
         &statics,
         crate::fixed::shiplayout::ShipLayout::Paladin.into(),
-    );
+    )
+    .unwrap();
     let mut assets = StationAssets {
         current_ship: Some(ship_a.clone()),
         ships: vec![ship_b.clone()],
@@ -0,0 +1,41 @@
+/// `SplitMix64` is small and stable which is all that is needed for the generators.
+/// General purpose random crates do not guarantee the same output across versions.
+pub struct Rng(u64);
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random number in `0..max`
+    pub fn below(&mut self, max: u32) -> u32 {
+        // The remainder is always below max and fits
+        u32::try_from(self.next() % u64::from(max.max(1))).unwrap_or(u32::MAX)
+    }
+
+    /// Random number in `0..max`
+    pub fn below_u8(&mut self, max: u8) -> u8 {
+        u8::try_from(self.below(u32::from(max))).unwrap_or(u8::MAX)
+    }
+
+    /// Random index of a slice with the given length
+    pub fn index(&mut self, len: usize) -> usize {
+        let len = u64::try_from(len.max(1)).unwrap_or(u64::MAX);
+        usize::try_from(self.next() % len).unwrap_or(usize::MAX)
+    }
+}
+
+/// FNV-1a to derive seeds from names independent of enum orders
+pub fn hash_name(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
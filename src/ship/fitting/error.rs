@@ -1,5 +1,9 @@
+use crate::fixed::StaticsError;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
+    Statics(StaticsError),
+
     Cpu { wants: u16, max: u16 },
     Powergrid { wants: u16, max: u16 },
     StructureZero,
@@ -12,6 +16,7 @@ pub enum Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Statics(err) => err.fmt(f),
             Self::Cpu { wants, max } => write!(
                 f,
                 "Not enough CPU: Wants {} but only {} are available.",
@@ -45,3 +50,9 @@ impl std::fmt::Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+impl From<StaticsError> for Error {
+    fn from(err: StaticsError) -> Self {
+        Self::Statics(err)
+    }
+}
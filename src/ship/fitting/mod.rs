@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::fixed::module::{Passive, Targeted, Untargeted};
 use crate::fixed::shiplayout::ShipLayout;
-use crate::fixed::Statics;
+use crate::fixed::{Statics, StaticsError};
 
 use super::Collateral;
 
@@ -12,6 +12,7 @@ pub use error::Error;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ShipFitting")]
 pub struct Fitting {
     pub layout: ShipLayout,
@@ -23,6 +24,7 @@ pub struct Fitting {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "ShipFittingInfrastructureUsage")]
 pub struct InfrastructureUsage {
     pub cpu: u16,
@@ -56,41 +58,43 @@ impl From<ShipLayout> for Fitting {
 }
 
 impl Fitting {
-    #[must_use]
-    pub fn to_usage(&self, statics: &Statics) -> InfrastructureUsage {
+    /// Sums up the infrastructure used by the modules
+    /// # Errors
+    /// Errors when a module is not part of the statics
+    pub fn to_usage(&self, statics: &Statics) -> Result<InfrastructureUsage, StaticsError> {
         let mut cpu = 0;
         let mut powergrid = 0;
         for id in &self.slots_targeted {
-            let m = statics.modules_targeted.get(id);
+            let m = statics.modules_targeted.try_get(id)?;
             cpu += m.required_cpu;
             powergrid += m.required_powergrid;
         }
         for id in &self.slots_untargeted {
-            let m = statics.modules_untargeted.get(id);
+            let m = statics.modules_untargeted.try_get(id)?;
             cpu += m.required_cpu;
             powergrid += m.required_powergrid;
         }
         for id in &self.slots_passive {
-            let m = statics.modules_passive.get(id);
+            let m = statics.modules_passive.try_get(id)?;
             cpu += m.required_cpu;
             powergrid += m.required_powergrid;
         }
 
-        InfrastructureUsage {
+        Ok(InfrastructureUsage {
             cpu,
             powergrid,
             slots_targeted: self.slots_targeted.len(),
             slots_untargeted: self.slots_untargeted.len(),
             slots_passive: self.slots_passive.len(),
-        }
+        })
     }
 
     /// Check if the fitting is valid or not
     /// # Errors
     /// When Fitting isnt valid the Error states why
     pub fn is_valid(&self, statics: &Statics) -> Result<(), Error> {
-        let usage = self.to_usage(statics);
-        let layout = statics.ship_layouts.get(&self.layout);
+        let usage = self.to_usage(statics)?;
+        let layout = statics.ship_layouts.try_get(&self.layout)?;
         // More modules than layout offers
         if usage.slots_targeted > layout.slots_targeted.into() {
             return Err(Error::TooManyTargetedModules {
@@ -126,7 +130,7 @@ impl Fitting {
         }
 
         // Dead on undock
-        let collateral = self.maximum_collateral(statics);
+        let collateral = self.maximum_collateral(statics)?;
         if !collateral.is_alive() {
             return Err(Error::StructureZero);
         }
@@ -134,9 +138,11 @@ impl Fitting {
         Ok(())
     }
 
-    #[must_use]
+    /// The collateral of the ship layout including the modules
+    /// # Errors
+    /// Errors when the layout or a module is not part of the statics
     #[allow(clippy::cast_sign_loss)]
-    pub fn maximum_collateral(&self, statics: &Statics) -> Collateral {
+    pub fn maximum_collateral(&self, statics: &Statics) -> Result<Collateral, StaticsError> {
         #[allow(clippy::cast_sign_loss)]
         const fn add(base: u16, add: i16) -> u16 {
             if add >= 0 {
@@ -147,14 +153,14 @@ impl Fitting {
             }
         }
 
-        let mut collateral = statics.ship_layouts.get(&self.layout).collateral;
+        let mut collateral = statics.ship_layouts.try_get(&self.layout)?.collateral;
 
         for id in &self.slots_passive {
-            let m = statics.modules_passive.get(id);
+            let m = statics.modules_passive.try_get(id)?;
             collateral.armor = add(collateral.armor, m.hitpoints_armor);
         }
 
-        collateral
+        Ok(collateral)
     }
 }
 
@@ -175,10 +181,22 @@ fn collateral_without_modules_correct() {
         slots_untargeted: vec![],
         slots_passive: vec![],
     };
-    let result = fitting.maximum_collateral(&statics);
+    let result = fitting.maximum_collateral(&statics).unwrap();
     assert_eq!(result, expected.collateral);
 }
 
+#[test]
+fn missing_layout_is_an_error() {
+    let mut statics = Statics::default();
+    statics.ship_layouts.data.remove(&ShipLayout::Abis);
+    let fitting = Fitting::from(ShipLayout::Abis);
+    assert!(matches!(
+        fitting.is_valid(&statics),
+        Err(Error::Statics(StaticsError::MissingKey { .. }))
+    ));
+    assert!(fitting.maximum_collateral(&statics).is_err());
+}
+
 #[test]
 #[allow(clippy::cast_sign_loss)]
 fn collateral_of_default_fitting_correct() {
@@ -186,7 +204,7 @@ fn collateral_of_default_fitting_correct() {
     let fitting = Fitting::default();
     let expected_layout = statics.ship_layouts.get(&fitting.layout);
     let expected_passive = statics.modules_passive.get(&fitting.slots_passive[0]);
-    let result = fitting.maximum_collateral(&statics);
+    let result = fitting.maximum_collateral(&statics).unwrap();
     assert_eq!(
         result,
         Collateral {
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::entity::{Collateral, Health};
-use crate::fixed::Statics;
+use crate::fixed::{Statics, StaticsError};
 use crate::serde_helper::is_default;
 use crate::storage::Storage;
 
@@ -15,6 +15,7 @@ pub use fitting::{
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Ship {
     pub fitting: Fitting,
@@ -39,35 +40,54 @@ impl Default for Ship {
 }
 
 impl Ship {
-    #[must_use]
-    pub fn new(statics: &Statics, fitting: Fitting) -> Self {
-        let collateral = fitting.maximum_collateral(statics);
-        Self {
+    /// Creates a new ship with the maximum collateral of the fitting
+    /// # Errors
+    /// Errors when the fitting contains something not part of the statics
+    pub fn new(statics: &Statics, fitting: Fitting) -> Result<Self, StaticsError> {
+        let collateral = fitting.maximum_collateral(statics)?;
+        Ok(Self {
             fitting,
             collateral,
             cargo: Storage::default(),
-        }
+        })
     }
 
-    #[must_use]
-    pub fn to_health(&self, statics: &Statics) -> Health {
-        let max = self.fitting.maximum_collateral(statics);
-        self.collateral.calc_health(max)
+    /// # Errors
+    /// Errors when the fitting contains something not part of the statics
+    pub fn to_health(&self, statics: &Statics) -> Result<Health, StaticsError> {
+        let max = self.fitting.maximum_collateral(statics)?;
+        Ok(self.collateral.calc_health(max))
     }
 
-    #[must_use]
-    pub fn free_cargo(&self, statics: &Statics) -> u32 {
-        let details = statics.ship_layouts.get(&self.fitting.layout);
-        details.cargo_slots.saturating_sub(self.cargo.total_slots())
+    /// Free volume in the cargo
+    /// # Errors
+    /// Errors when the ship layout or an item in the cargo is not part of the statics
+    pub fn free_cargo(&self, statics: &Statics) -> Result<u32, StaticsError> {
+        let details = statics.ship_layouts.try_get(&self.fitting.layout)?;
+        let used = self.cargo.total_volume(statics)?;
+        Ok(details.cargo_volume.saturating_sub(used))
     }
 }
 
+#[test]
+fn free_cargo_is_in_volume() {
+    use crate::fixed::item::Ore;
+    let statics = Statics::default();
+    let mut ship = Ship::new(&statics, Fitting::default()).unwrap();
+    let max = statics.ship_layouts.get(&ship.fitting.layout).cargo_volume;
+    assert_eq!(ship.free_cargo(&statics), Ok(max));
+
+    ship.cargo.saturating_add(Ore::Aromit, 2);
+    let ore = statics.items.get(&Ore::Aromit.into()).volume;
+    assert_eq!(ship.free_cargo(&statics), Ok(max - 2 * ore));
+}
+
 #[test]
 fn default_ship_is_exactly_from_statics() {
     let statics = Statics::default();
     let expected = Ship {
         fitting: Fitting::default(),
-        collateral: Fitting::default().maximum_collateral(&statics),
+        collateral: Fitting::default().maximum_collateral(&statics).unwrap(),
         cargo: Storage::default(),
     };
 
@@ -0,0 +1,159 @@
+use crate::fixed::item::Ore;
+use crate::fixed::solarsystem::Solarsystem;
+use crate::random::{hash_name, Rng};
+
+use super::{Entity, Site};
+
+/// Generated asteroid field with its asteroids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsteroidField {
+    pub planet: u8,
+    pub site: Site,
+    pub asteroids: Vec<Entity>,
+}
+
+/// Generates the asteroid fields of a solarsystem.
+///
+/// The same input always results in the same fields, so a server only needs to remember the seed.
+/// Lower security results in more fields, bigger asteroids and rarer ores.
+/// A solarsystem without planets has no asteroid fields.
+#[must_use]
+pub fn generate(
+    solarsystem: Solarsystem,
+    planets: u8,
+    security: u8,
+    seed: u64,
+) -> Vec<AsteroidField> {
+    if planets == 0 {
+        return Vec::new();
+    }
+    let mut rng = Rng::new(seed ^ hash_name(&solarsystem.to_string()));
+    let insecurity = 100 - u32::from(security.min(100));
+
+    let amount = 1 + rng.below(2 + insecurity / 40);
+    let mut fields: Vec<AsteroidField> = Vec::new();
+    while fields.len() < amount as usize {
+        // Zero is not used to keep the numbers nice to read for humans
+        let unique = 1 + rng.below_u8(255);
+        let site = Site::AsteroidField(unique);
+        if fields.iter().any(|o| o.site == site) {
+            continue;
+        }
+
+        let planet = 1 + rng.below_u8(planets);
+        let asteroids = generate_asteroids(&mut rng, security);
+        fields.push(AsteroidField {
+            planet,
+            site,
+            asteroids,
+        });
+    }
+    fields
+}
+
+/// Generates the asteroids of a single field.
+///
+/// Can be used to refill a depleted field.
+#[must_use]
+pub fn generate_asteroids_of_field(security: u8, seed: u64) -> Vec<Entity> {
+    generate_asteroids(&mut Rng::new(seed), security)
+}
+
+/// Generates a single asteroid.
+///
+/// Can be used to respawn a collapsed asteroid.
+#[must_use]
+pub fn generate_asteroid(security: u8, seed: u64) -> Entity {
+    asteroid(&mut Rng::new(seed), security)
+}
+
+fn generate_asteroids(rng: &mut Rng, security: u8) -> Vec<Entity> {
+    let amount = 3 + rng.below(4);
+    (0..amount).map(|_| asteroid(rng, security)).collect()
+}
+
+fn asteroid(rng: &mut Rng, security: u8) -> Entity {
+    let insecurity = 100 - u32::from(security.min(100));
+    let ores = ores_of_security(security);
+    let ore = ores[rng.index(ores.len())];
+    let remaining_ore = (50 + rng.below(101)) * (100 + insecurity) / 100;
+    let structure = (20 + rng.below(41)) * (100 + insecurity) / 100;
+    Entity::new_asteroid(
+        ore,
+        remaining_ore,
+        u16::try_from(structure).unwrap_or(u16::MAX),
+    )
+}
+
+/// Ores which can be found in a solarsystem of the given security
+#[must_use]
+pub const fn ores_of_security(security: u8) -> &'static [Ore] {
+    match security {
+        75.. => &[Ore::Aromit, Ore::Solmit],
+        50..=74 => &[Ore::Aromit, Ore::Solmit, Ore::Tormit],
+        25..=49 => &[Ore::Solmit, Ore::Tormit, Ore::Vesmit],
+        _ => &[Ore::Tormit, Ore::Vesmit],
+    }
+}
+
+#[test]
+fn same_seed_same_fields() {
+    let a = generate(Solarsystem::Arama, 2, 51, 42);
+    let b = generate(Solarsystem::Arama, 2, 51, 42);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn different_seed_different_fields() {
+    let a = generate(Solarsystem::Arama, 2, 51, 42);
+    let b = generate(Solarsystem::Arama, 2, 51, 1337);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn different_solarsystem_different_fields() {
+    let a = generate(Solarsystem::Arama, 6, 50, 42);
+    let b = generate(Solarsystem::Wabinihwa, 6, 50, 42);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn no_planets_no_fields() {
+    assert!(generate(Solarsystem::Arama, 0, 10, 42).is_empty());
+}
+
+#[test]
+fn fields_are_valid() {
+    for seed in 0..100 {
+        let fields = generate(Solarsystem::Vosu, 3, 10, seed);
+        assert!(!fields.is_empty());
+        for (i, field) in fields.iter().enumerate() {
+            assert!((1..=3).contains(&field.planet));
+            assert!(!field.asteroids.is_empty());
+            assert!(!fields[i + 1..].iter().any(|o| o.site == field.site));
+            for asteroid in &field.asteroids {
+                if let Entity::Asteroid(asteroid) = asteroid {
+                    assert!(ores_of_security(10).contains(&asteroid.ore));
+                    assert!(!asteroid.is_collapsed());
+                } else {
+                    panic!("only asteroids expected {:?}", asteroid);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn low_security_has_more_ore() {
+    fn total_ore(security: u8) -> u32 {
+        (0..50)
+            .flat_map(|seed| generate(Solarsystem::Liagi, 4, security, seed))
+            .flat_map(|field| field.asteroids)
+            .map(|asteroid| match asteroid {
+                Entity::Asteroid(asteroid) => asteroid.remaining_ore,
+                _ => 0,
+            })
+            .sum()
+    }
+    assert!(total_ore(5) > total_ore(95));
+}
@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+
+use super::asteroid_field::{generate_asteroid, generate_asteroids_of_field};
+use super::{Actor, Entity, Log, Population, Site};
+
+/// How long it takes for asteroids to come back
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Rounds until a single collapsed asteroid respawns in its field
+    pub asteroid_respawn_rounds: u32,
+    /// Rounds until a field without any asteroids left respawns completely
+    pub field_respawn_rounds: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            asteroid_respawn_rounds: 30,
+            field_respawn_rounds: 100,
+        }
+    }
+}
+
+/// Keeps track of depleted asteroids and fields of a solarsystem and respawns them.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Lifecycle {
+    round: u64,
+    depleted: Vec<Depleted>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Depleted {
+    site: Site,
+    rounds_left: u32,
+    kind: DepletedKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum DepletedKind {
+    Asteroid,
+    Field { planet: u8 },
+}
+
+impl Lifecycle {
+    /// Remembers the asteroids which are gone after a round of the site.
+    /// Pass the `log` of the `site::advance` output.
+    pub fn record(&mut self, config: Config, site: Site, log: &[Log]) {
+        for entry in log {
+            if let Log::Collapse(Actor::Asteroid(_))
+            | Log::RapidUnscheduledDisassembly(Actor::Asteroid(_)) = entry
+            {
+                self.depleted.push(Depleted {
+                    site,
+                    rounds_left: config.asteroid_respawn_rounds,
+                    kind: DepletedKind::Asteroid,
+                });
+            }
+        }
+    }
+
+    /// Amount of asteroids and fields waiting to respawn
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.depleted.len()
+    }
+
+    /// Advances the lifecycle by one round.
+    ///
+    /// Fields without asteroids are removed from the population when nothing else is there.
+    /// Respawned asteroids and fields are added to the population.
+    /// The seed results in the same asteroids for the same rounds.
+    ///
+    /// # Returns
+    /// The logs of the sites which changed
+    pub fn advance(
+        &mut self,
+        config: Config,
+        population: &mut Population,
+        security: u8,
+        seed: u64,
+    ) -> Vec<(Site, Log)> {
+        self.round += 1;
+        self.deplete_fields(config, population);
+
+        let mut log = Vec::new();
+        let mut remaining = Vec::new();
+        for mut depleted in std::mem::take(&mut self.depleted) {
+            depleted.rounds_left = depleted.rounds_left.saturating_sub(1);
+            if depleted.rounds_left > 0 {
+                remaining.push(depleted);
+                continue;
+            }
+
+            let seed =
+                seed ^ self.round.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ unique(depleted.site);
+            let asteroids = match depleted.kind {
+                DepletedKind::Asteroid => vec![generate_asteroid(security, seed)],
+                DepletedKind::Field { planet } => {
+                    if population.sites.planet(depleted.site).is_none() {
+                        population.sites.add(planet, depleted.site);
+                    }
+                    generate_asteroids_of_field(security, seed)
+                }
+            };
+            for asteroid in asteroids {
+                log.push((depleted.site, Log::Respawn(Actor::from(&asteroid))));
+                population
+                    .entities
+                    .entry(depleted.site)
+                    .or_default()
+                    .push(asteroid);
+            }
+        }
+        self.depleted = remaining;
+        log
+    }
+
+    /// Fields without asteroids respawn as a whole instead of asteroid by asteroid
+    fn deplete_fields(&mut self, config: Config, population: &mut Population) {
+        let mut sites = self
+            .depleted
+            .iter()
+            .filter(|o| matches!(o.kind, DepletedKind::Asteroid))
+            .map(|o| o.site)
+            .collect::<Vec<_>>();
+        sites.sort();
+        sites.dedup();
+
+        for site in sites {
+            let entities = population.entities_at(site);
+            if entities.iter().any(|o| matches!(o, Entity::Asteroid(_))) {
+                continue;
+            }
+            let Some(planet) = population.sites.planet(site) else {
+                continue;
+            };
+
+            if entities.is_empty() {
+                population.sites.remove(site);
+                population.entities.remove(&site);
+            }
+
+            self.depleted.retain(|o| o.site != site);
+            self.depleted.push(Depleted {
+                site,
+                rounds_left: config.field_respawn_rounds,
+                kind: DepletedKind::Field { planet },
+            });
+        }
+    }
+}
+
+/// Asteroids only exist in asteroid fields so other sites do not need to be told apart
+fn unique(site: Site) -> u64 {
+    if let Site::AsteroidField(unique) = site {
+        u64::from(unique)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+fn example() -> (Population, Site) {
+    let site = Site::AsteroidField(7);
+    let mut population = Population::default();
+    population.add_asteroid_fields(vec![super::asteroid_field::AsteroidField {
+        planet: 2,
+        site,
+        asteroids: vec![
+            Entity::new_asteroid(crate::fixed::item::Ore::Aromit, 10, 10),
+            Entity::new_asteroid(crate::fixed::item::Ore::Solmit, 10, 10),
+        ],
+    }]);
+    (population, site)
+}
+
+#[cfg(test)]
+const CONFIG: Config = Config {
+    asteroid_respawn_rounds: 2,
+    field_respawn_rounds: 3,
+};
+
+#[test]
+fn respawns_asteroid_after_rounds() {
+    let (mut population, site) = example();
+    let mut lifecycle = Lifecycle::default();
+
+    let collapsed = population.entities.get_mut(&site).unwrap().remove(0);
+    lifecycle.record(CONFIG, site, &[Log::Collapse(Actor::from(&collapsed))]);
+    assert_eq!(lifecycle.pending(), 1);
+
+    let log = lifecycle.advance(CONFIG, &mut population, 80, 42);
+    assert!(log.is_empty());
+    assert_eq!(population.entities_at(site).len(), 1);
+
+    let log = lifecycle.advance(CONFIG, &mut population, 80, 42);
+    assert_eq!(log.len(), 1);
+    assert!(matches!(log[0], (s, Log::Respawn(Actor::Asteroid(_))) if s == site));
+    assert_eq!(population.entities_at(site).len(), 2);
+    assert_eq!(lifecycle.pending(), 0);
+}
+
+#[test]
+fn respawns_whole_field_when_empty() {
+    let (mut population, site) = example();
+    let mut lifecycle = Lifecycle::default();
+
+    let log = population
+        .entities
+        .remove(&site)
+        .unwrap()
+        .iter()
+        .map(|o| Log::Collapse(o.into()))
+        .collect::<Vec<_>>();
+    lifecycle.record(CONFIG, site, &log);
+    assert_eq!(lifecycle.pending(), 2);
+
+    assert!(lifecycle
+        .advance(CONFIG, &mut population, 80, 42)
+        .is_empty());
+    assert_eq!(lifecycle.pending(), 1);
+    assert_eq!(population.sites.planet(site), None);
+
+    assert!(lifecycle
+        .advance(CONFIG, &mut population, 80, 42)
+        .is_empty());
+    let log = lifecycle.advance(CONFIG, &mut population, 80, 42);
+    assert!(log.len() >= 3);
+    assert_eq!(population.sites.planet(site), Some(2));
+    assert_eq!(population.entities_at(site).len(), log.len());
+    assert_eq!(lifecycle.pending(), 0);
+}
+
+#[test]
+fn keeps_field_with_players_until_respawn() {
+    let (mut population, site) = example();
+    let mut lifecycle = Lifecycle::default();
+
+    let player = Entity::Player((
+        crate::player::Player::Telegram(666),
+        crate::ship::Ship::default(),
+    ));
+    let log = std::mem::replace(
+        population.entities.get_mut(&site).unwrap(),
+        vec![player.clone()],
+    )
+    .iter()
+    .map(|o| Log::Collapse(o.into()))
+    .collect::<Vec<_>>();
+    lifecycle.record(CONFIG, site, &log);
+
+    for _ in 0..3 {
+        lifecycle.advance(CONFIG, &mut population, 80, 42);
+    }
+    let entities = population.entities_at(site);
+    assert_eq!(entities[0], player);
+    assert!(entities.len() > 1);
+}
+
+#[test]
+fn ignores_other_logs() {
+    let mut lifecycle = Lifecycle::default();
+    lifecycle.record(
+        CONFIG,
+        Site::AsteroidField(1),
+        &[Log::WarpIn(Actor::Player((
+            crate::player::Player::Telegram(666),
+            crate::fixed::shiplayout::ShipLayout::default(),
+        )))],
+    );
+    assert_eq!(lifecycle.pending(), 0);
+}
+
+#[test]
+fn can_parse_lifecycle() {
+    let (mut population, site) = example();
+    let mut lifecycle = Lifecycle::default();
+    lifecycle.record(
+        CONFIG,
+        site,
+        &[Log::Collapse(Actor::Asteroid(
+            crate::fixed::item::Ore::Aromit,
+        ))],
+    );
+    population.entities.remove(&site);
+    lifecycle.advance(CONFIG, &mut population, 80, 42);
+    crate::test_helper::can_serde_parse(&lifecycle);
+}
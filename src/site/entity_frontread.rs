@@ -4,10 +4,11 @@ use crate::entity::Health;
 use crate::fixed::item::Ore;
 use crate::fixed::npc_faction::NpcFaction;
 use crate::fixed::shiplayout::ShipLayout;
-use crate::fixed::{facility, Statics};
+use crate::fixed::{facility, Statics, StaticsError};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum SiteEntity {
     Asteroid(Asteroid),
@@ -18,6 +19,7 @@ pub enum SiteEntity {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteEntityAsteroid")]
 pub struct Asteroid {
     pub ore: Ore,
@@ -27,6 +29,7 @@ pub struct Asteroid {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteEntityFacility")]
 pub struct Facility {
     pub facility: facility::Facility,
@@ -34,6 +37,7 @@ pub struct Facility {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteEntityNpc")]
 pub struct Npc {
     pub faction: NpcFaction,
@@ -44,6 +48,7 @@ pub struct Npc {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteEntityPlayer")]
 pub struct Player {
     pub player: crate::player::Player,
@@ -53,9 +58,10 @@ pub struct Player {
 }
 
 impl SiteEntity {
-    #[must_use]
-    pub fn from(statics: &Statics, entity: &crate::site::Entity) -> Self {
-        match entity {
+    /// # Errors
+    /// Errors when the ship of the entity contains something not part of the statics
+    pub fn from(statics: &Statics, entity: &crate::site::Entity) -> Result<Self, StaticsError> {
+        Ok(match entity {
             crate::site::Entity::Asteroid(a) => Self::Asteroid(Asteroid {
                 ore: a.ore,
                 health: a.collateral.calc_health_raw(0, a.max_structure),
@@ -64,14 +70,14 @@ impl SiteEntity {
             crate::site::Entity::Npc((faction, ship)) => Self::Npc(Npc {
                 faction: *faction,
                 shiplayout: ship.fitting.layout,
-                health: ship.to_health(statics),
+                health: ship.to_health(statics)?,
             }),
             crate::site::Entity::Player((player, ship)) => Self::Player(Player {
                 player: *player,
                 shiplayout: ship.fitting.layout,
-                health: ship.to_health(statics),
+                health: ship.to_health(statics)?,
             }),
-        }
+        })
     }
 }
 
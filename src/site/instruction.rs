@@ -8,6 +8,7 @@ use super::Site;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(
     rename_all = "camelCase",
     rename = "SiteInstruction",
@@ -24,6 +25,7 @@ pub enum Instruction {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteInstructionModuleUntargeted")]
 pub struct UseModuleUntargeted {
     pub module_index: u8,
@@ -31,6 +33,7 @@ pub struct UseModuleUntargeted {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteInstructionModuleTargeted")]
 pub struct UseModuleTargeted {
     pub target_index_in_site: u8,
@@ -39,6 +42,7 @@ pub struct UseModuleTargeted {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteInstructionFacility")]
 pub struct UseFacilityService {
     pub target_index_in_site: u8,
@@ -47,6 +51,7 @@ pub struct UseFacilityService {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteInstructionWarp")]
 pub struct Warp {
     pub target: Site,
@@ -99,7 +104,7 @@ where
             result.push((*entity, instruction));
         }
     }
-    result.sort_by(|a, b| a.1.cmp(&b.1));
+    result.sort_by_key(|a| a.1);
     result
 }
 
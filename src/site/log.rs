@@ -11,6 +11,7 @@ use super::Entity;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(
     rename_all = "camelCase",
     rename = "SiteLog",
@@ -19,8 +20,11 @@ use super::Entity;
 )]
 pub enum Log {
     ModuleTargeted((Actor, Targeted, Actor)),
+    /// Ore which ended up in the cargo of the actor
+    Mined((Actor, Ore, u32)),
 
     Collapse(Actor),
+    Respawn(Actor),
     Jump(Actor),
     RapidUnscheduledDisassembly(Actor),
 
@@ -33,6 +37,7 @@ pub enum Log {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", rename = "SiteLogActor", untagged)]
 pub enum Actor {
     Asteroid(Ore),
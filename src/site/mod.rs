@@ -1,11 +1,15 @@
+pub mod asteroid_field;
+pub mod belt;
 mod entity;
 pub mod entity_frontread;
 pub mod instruction;
 mod log;
+mod population;
 mod round;
 mod sites;
 
 pub use entity::{Entity, EntityAsteroid};
 pub use log::{Actor, Log};
+pub use population::Population;
 pub use round::{advance, Output};
-pub use sites::{Site, SitesNearPlanet};
+pub use sites::{planet_of, warp_rounds, Site, SitesNearPlanet};
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use crate::fixed::facility::Facility;
+use crate::fixed::solarsystem::Solarsystem;
+use crate::fixed::{Statics, StaticsError};
+use crate::universe::Wormhole;
+
+use super::asteroid_field::AsteroidField;
+use super::{Entity, Site, SitesNearPlanet};
+
+/// All the sites of a solarsystem and the entities at each of them
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Population {
+    pub sites: SitesNearPlanet,
+    pub entities: BTreeMap<Site, Vec<Entity>>,
+}
+
+impl Population {
+    /// The sites of a solarsystem when nothing happened yet.
+    ///
+    /// Every station and stargate is placed at its planet with its facility as the only entity.
+    /// # Errors
+    /// Errors when the solarsystem or a facility is not part of the statics
+    /// or the solarsystem has more stations than a [`Site::Station`] can identify
+    pub fn initial(statics: &Statics, solarsystem: Solarsystem) -> Result<Self, StaticsError> {
+        let details = statics.solarsystems.try_get(&solarsystem)?;
+        let mut population = Self::default();
+
+        for (index, planet) in details.stations.iter().enumerate() {
+            let index =
+                u8::try_from(index).map_err(|_| StaticsError::TooManyStations(solarsystem))?;
+            population.add_facility(statics, *planet, Site::Station(index), Facility::Station)?;
+        }
+
+        for (target, planet) in &details.stargates {
+            population.add_facility(
+                statics,
+                *planet,
+                Site::Stargate(*target),
+                Facility::Stargate,
+            )?;
+        }
+
+        Ok(population)
+    }
+
+    fn add_facility(
+        &mut self,
+        statics: &Statics,
+        planet: u8,
+        site: Site,
+        facility: Facility,
+    ) -> Result<(), StaticsError> {
+        statics.facilities.try_get(&facility)?;
+        self.sites.add(planet, site);
+        self.entities
+            .entry(site)
+            .or_default()
+            .push(Entity::Facility(facility));
+        Ok(())
+    }
+
+    /// Adds generated asteroid fields, see `asteroid_field::generate`.
+    pub fn add_asteroid_fields(&mut self, fields: Vec<AsteroidField>) {
+        for field in fields {
+            self.sites.add(field.planet, field.site);
+            self.entities
+                .entry(field.site)
+                .or_default()
+                .extend(field.asteroids);
+        }
+    }
+
+    /// Adds the wormhole site with its facility when the wormhole ends in the solarsystem
+    pub fn add_wormhole(&mut self, solarsystem: Solarsystem, wormhole: &Wormhole) {
+        if let Some((planet, site)) = wormhole.site_in(solarsystem) {
+            self.sites.add(planet, site);
+            self.entities
+                .entry(site)
+                .or_default()
+                .push(Entity::Facility(Facility::Wormhole));
+        }
+    }
+
+    /// Removes the facility of a collapsed wormhole.
+    /// The site is removed too when nothing else is there anymore.
+    pub fn remove_wormhole(&mut self, solarsystem: Solarsystem, wormhole: &Wormhole) {
+        if let Some((_, site)) = wormhole.site_in(solarsystem) {
+            if let Some(entities) = self.entities.get_mut(&site) {
+                entities.retain(|o| o != &Entity::Facility(Facility::Wormhole));
+                if entities.is_empty() {
+                    self.entities.remove(&site);
+                }
+            }
+            if !self.entities.contains_key(&site) {
+                self.sites.remove(site);
+            }
+        }
+    }
+
+    /// The entities at the given site, ready for `site::advance`
+    #[must_use]
+    pub fn entities_at(&self, site: Site) -> &[Entity] {
+        self.entities.get(&site).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[test]
+fn initial_places_stations_and_stargates() -> Result<(), StaticsError> {
+    let statics = Statics::default();
+    let population = Population::initial(&statics, Solarsystem::Wabinihwa)?;
+
+    assert_eq!(
+        population.sites.all(),
+        [
+            Site::Station(0),
+            Site::Station(2),
+            Site::Station(1),
+            Site::Stargate(Solarsystem::Liagi),
+            Site::Station(3),
+            Site::Stargate(Solarsystem::Arama),
+        ]
+    );
+    assert_eq!(population.sites.planet(Site::Station(1)), Some(3));
+    assert_eq!(
+        population.entities_at(Site::Station(1)),
+        [Entity::Facility(Facility::Station)]
+    );
+    assert_eq!(
+        population.entities_at(Site::Stargate(Solarsystem::Arama)),
+        [Entity::Facility(Facility::Stargate)]
+    );
+    assert!(population.entities_at(Site::AsteroidField(1)).is_empty());
+    Ok(())
+}
+
+#[test]
+fn initial_errors_on_too_many_stations() {
+    let mut statics = Statics::default();
+    let details = statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap();
+    details.stations = vec![1; 257];
+    assert_eq!(
+        Population::initial(&statics, Solarsystem::Vosu),
+        Err(StaticsError::TooManyStations(Solarsystem::Vosu))
+    );
+}
+
+#[test]
+fn initial_is_ready_for_advance() -> Result<(), StaticsError> {
+    let statics = Statics::default();
+    for solarsystem in statics.solarsystems.data.keys() {
+        let population = Population::initial(&statics, *solarsystem)?;
+        for (site, entities) in &population.entities {
+            let output = super::advance(
+                &statics,
+                *solarsystem,
+                *site,
+                &population.sites,
+                &[],
+                entities,
+                &std::collections::HashMap::new(),
+            )?;
+            assert_eq!(&output.remaining, entities);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn can_add_asteroid_fields() -> Result<(), StaticsError> {
+    let statics = Statics::default();
+    let mut population = Population::initial(&statics, Solarsystem::Arama)?;
+    let fields = super::asteroid_field::generate(Solarsystem::Arama, 2, 51, 42);
+    let expected = fields.clone();
+    population.add_asteroid_fields(fields);
+    for field in expected {
+        assert_eq!(population.sites.planet(field.site), Some(field.planet));
+        assert_eq!(population.entities_at(field.site), field.asteroids);
+    }
+    Ok(())
+}
+
+#[test]
+fn can_add_and_remove_wormhole() -> Result<(), StaticsError> {
+    use crate::universe::WormholeEnd;
+    let statics = Statics::default();
+    let mut population = Population::initial(&statics, Solarsystem::Vosu)?;
+    let initial = population.clone();
+    let wormhole = Wormhole::new(
+        WormholeEnd {
+            solarsystem: Solarsystem::Wabinihwa,
+            planet: 1,
+        },
+        WormholeEnd {
+            solarsystem: Solarsystem::Vosu,
+            planet: 2,
+        },
+        10,
+        10,
+    );
+    let site = Site::Wormhole(Solarsystem::Wabinihwa);
+
+    population.add_wormhole(Solarsystem::Vosu, &wormhole);
+    assert_eq!(population.sites.planet(site), Some(2));
+    assert_eq!(
+        population.entities_at(site),
+        [Entity::Facility(Facility::Wormhole)]
+    );
+
+    population.remove_wormhole(Solarsystem::Vosu, &wormhole);
+    assert_eq!(population.sites.planet(site), None);
+    assert_eq!(population.sites.all(), initial.sites.all());
+    assert_eq!(population.entities, initial.entities);
+    Ok(())
+}
@@ -22,7 +22,7 @@ const fn saturating_apply(mut collateral: Collateral, effect: RoundEffect) -> Co
             collateral
         }
         RoundEffect::CapacitorRecharge(amount) => {
-            collateral.capacitor = collateral.capacitor.saturating_add(amount as u16);
+            collateral.capacitor = collateral.capacitor.saturating_add(amount);
             collateral
         }
         RoundEffect::ArmorRepair(amount) => {
@@ -3,14 +3,16 @@ use std::collections::HashMap;
 use crate::entity::Collateral;
 use crate::fixed::facility::Service;
 use crate::fixed::solarsystem::Solarsystem;
-use crate::fixed::Statics;
+use crate::fixed::{Statics, StaticsError};
+use crate::player::location::PlayerLocationWarp;
 use crate::player::Player;
 use crate::ship::Ship;
+use crate::universe::Wormhole;
 
 use self::effect::apply_passives;
 
 use super::instruction::Instruction;
-use super::{Entity, Log, Site};
+use super::{Entity, Log, Site, SitesNearPlanet};
 
 mod effect;
 mod module;
@@ -21,19 +23,26 @@ pub struct Output {
 
     pub dead: Vec<Player>,
     pub docking: Vec<(Solarsystem, u8, Entity)>,
-    pub warping_out: Vec<(Solarsystem, Site, Entity)>,
+    /// Entities which left the site together with their warp or jump.
+    /// The warp has its `eta` set to the rounds until arrival.
+    pub warping_out: Vec<(Entity, PlayerLocationWarp)>,
 
     pub log: Vec<Log>,
 }
 
-#[must_use]
+/// Advances the site by one round.
+/// The wormholes are the ones of the solarsystem, only needed when the site is a wormhole.
+/// # Errors
+/// Errors when an entity uses something not part of the statics
 pub fn advance(
     statics: &Statics,
     solarsystem: Solarsystem,
     site: Site,
+    sites: &SitesNearPlanet,
+    wormholes: &[Wormhole],
     entities: &[Entity],
     instructions: &HashMap<usize, Vec<Instruction>>,
-) -> Output {
+) -> Result<Output, StaticsError> {
     let mut entities = entities
         .iter()
         .enumerate()
@@ -48,7 +57,7 @@ pub fn advance(
         match instruction {
             Instruction::ModuleUntargeted(i) => {
                 if let Some(entity) = entities.get_mut(&actor) {
-                    module::apply_untargeted(statics, entity, i.module_index);
+                    module::apply_untargeted(statics, entity, i.module_index)?;
                 }
             }
             Instruction::ModuleTargeted(i) => module::apply_targeted(
@@ -58,7 +67,7 @@ pub fn advance(
                 i.module_index,
                 i.target_index_in_site,
                 &mut log,
-            ),
+            )?,
             Instruction::SelfDestruct => {
                 if let Some(entity) = entities.get_mut(&actor) {
                     module::self_destruct(entity);
@@ -78,6 +87,7 @@ pub fn advance(
                 Service::Jump => movement::jump(
                     solarsystem,
                     site,
+                    wormholes,
                     &mut entities,
                     actor,
                     &mut warping_out,
@@ -85,21 +95,25 @@ pub fn advance(
                 ),
             },
             Instruction::Warp(instruction) => movement::warp_out(
+                statics,
                 solarsystem,
+                site,
+                sites,
                 &mut entities,
                 actor,
                 instruction.target,
                 &mut warping_out,
                 &mut log,
-            ),
+            )?,
         }
     }
 
-    let (dead, remaining) = finishup_entities(statics, &entities, &mut log);
+    let (dead, remaining) = finishup_entities(statics, &entities, &mut log)?;
 
-    // TODO: cleanup instructions. Warp for example has to stay there but there is a timer needed for that
+    // Warping entities leave the site right away.
+    // Their PlayerLocationWarp in warping_out has to be advanced every round until it arrived.
 
-    Output {
+    Ok(Output {
         remaining,
 
         dead,
@@ -107,7 +121,7 @@ pub fn advance(
         warping_out,
 
         log,
-    }
+    })
 }
 
 /// - apply passive effects
@@ -117,7 +131,7 @@ fn finishup_entities(
     statics: &Statics,
     entities: &HashMap<usize, Entity>,
     log: &mut Vec<Log>,
-) -> (Vec<Player>, Vec<Entity>) {
+) -> Result<(Vec<Player>, Vec<Entity>), StaticsError> {
     let mut remaining = Vec::new();
     let mut dead = Vec::new();
 
@@ -140,7 +154,7 @@ fn finishup_entities(
                 }
             }
             Entity::Npc((faction, ship)) => {
-                let collateral = apply_passives_and_limit_to_ship_maximum(statics, ship);
+                let collateral = apply_passives_and_limit_to_ship_maximum(statics, ship)?;
                 if collateral.is_alive() {
                     remaining.push(Entity::Npc((
                         *faction,
@@ -154,7 +168,7 @@ fn finishup_entities(
                 }
             }
             Entity::Player((player, ship)) => {
-                let collateral = apply_passives_and_limit_to_ship_maximum(statics, ship);
+                let collateral = apply_passives_and_limit_to_ship_maximum(statics, ship)?;
                 if collateral.is_alive() {
                     remaining.push(Entity::Player((
                         *player,
@@ -170,11 +184,94 @@ fn finishup_entities(
             }
         }
     }
-    (dead, remaining)
+    Ok((dead, remaining))
+}
+
+fn apply_passives_and_limit_to_ship_maximum(
+    statics: &Statics,
+    ship: &Ship,
+) -> Result<Collateral, StaticsError> {
+    let layout = statics.ship_layouts.try_get(&ship.fitting.layout)?;
+    let max = ship.fitting.maximum_collateral(statics)?;
+    Ok(apply_passives(ship.collateral, &layout.round_effects).min(max))
 }
 
-fn apply_passives_and_limit_to_ship_maximum(statics: &Statics, ship: &Ship) -> Collateral {
-    let layout = statics.ship_layouts.get(&ship.fitting.layout);
-    let max = ship.fitting.maximum_collateral(statics);
-    apply_passives(ship.collateral, &layout.round_effects).min(max)
+#[test]
+fn warp_has_eta_of_planet_distance() -> Result<(), StaticsError> {
+    use super::instruction::Warp;
+    use super::population::Population;
+    let statics = Statics::default();
+    let population = Population::initial(&statics, Solarsystem::Wabinihwa)?;
+    let player = Player::Telegram(666);
+    let entities = vec![
+        Entity::Facility(crate::fixed::facility::Facility::Station),
+        Entity::Player((player, Ship::default())),
+    ];
+    let mut instructions = HashMap::new();
+    instructions.insert(
+        1,
+        vec![Instruction::Warp(Warp {
+            target: Site::Stargate(Solarsystem::Arama),
+        })],
+    );
+
+    // Station 0 at planet 2, Stargate to Arama at planet 4
+    let output = advance(
+        &statics,
+        Solarsystem::Wabinihwa,
+        Site::Station(0),
+        &population.sites,
+        &[],
+        &entities,
+        &instructions,
+    )?;
+    assert_eq!(output.remaining, [entities[0].clone()]);
+    assert_eq!(output.warping_out.len(), 1);
+    let (entity, warp) = &output.warping_out[0];
+    assert_eq!(entity, &entities[1]);
+    assert_eq!(
+        warp,
+        &PlayerLocationWarp::new(
+            Solarsystem::Wabinihwa,
+            Site::Stargate(Solarsystem::Arama),
+            2
+        )
+    );
+    assert_eq!((warp.progress, warp.eta), (0, 2));
+    Ok(())
+}
+
+#[test]
+fn warp_to_unknown_planet_takes_one_round() -> Result<(), StaticsError> {
+    use super::instruction::Warp;
+    use super::population::Population;
+    let statics = Statics::default();
+    let population = Population::initial(&statics, Solarsystem::Wabinihwa)?;
+    let entities = vec![Entity::Player((Player::Telegram(666), Ship::default()))];
+    let mut instructions = HashMap::new();
+    instructions.insert(
+        0,
+        vec![Instruction::Warp(Warp {
+            target: Site::AsteroidField(250),
+        })],
+    );
+
+    let output = advance(
+        &statics,
+        Solarsystem::Wabinihwa,
+        Site::Station(0),
+        &population.sites,
+        &[],
+        &entities,
+        &instructions,
+    )?;
+    assert!(output.remaining.is_empty());
+    assert_eq!(
+        output.warping_out,
+        [(
+            entities[0].clone(),
+            PlayerLocationWarp::new(Solarsystem::Wabinihwa, Site::AsteroidField(250), 1)
+        )]
+    );
+    Ok(())
 }
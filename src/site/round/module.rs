@@ -4,7 +4,7 @@ use crate::entity::Collateral;
 use crate::fixed::item::Item;
 use crate::fixed::module::Targeted;
 use crate::fixed::round_effect::RoundEffect;
-use crate::fixed::{module, Statics};
+use crate::fixed::{module, Statics, StaticsError};
 use crate::site::{Actor, Entity, Log};
 
 use super::effect::{apply_to_origin, apply_to_target};
@@ -18,7 +18,11 @@ pub fn self_destruct(entity: &mut Entity) {
     *collateral = Collateral::DEAD;
 }
 
-pub fn apply_untargeted(statics: &Statics, entity: &mut Entity, module_index: u8) {
+pub fn apply_untargeted(
+    statics: &Statics,
+    entity: &mut Entity,
+    module_index: u8,
+) -> Result<(), StaticsError> {
     let ship = match entity {
         Entity::Facility(_) | Entity::Asteroid(_) => {
             unreachable!("Only ships can use modules {:?}", entity)
@@ -29,7 +33,8 @@ pub fn apply_untargeted(statics: &Statics, entity: &mut Entity, module_index: u8
         .fitting
         .slots_untargeted
         .get(module_index as usize)
-        .map(|o| statics.modules_untargeted.get(o))
+        .map(|o| statics.modules_untargeted.try_get(o))
+        .transpose()?
     {
         if let Some(result) = apply_to_origin(ship.collateral, &module.effects) {
             ship.collateral = result;
@@ -40,14 +45,14 @@ pub fn apply_untargeted(statics: &Statics, entity: &mut Entity, module_index: u8
             module_index, ship.fitting
         );
     }
+    Ok(())
 }
 
-#[must_use]
 fn apply_targeted_to_origin<'s>(
     statics: &'s Statics,
     entity: &mut Entity,
     module_index: u8,
-) -> Option<(Actor, Targeted, &'s module::TargetedDetails, u32)> {
+) -> Result<Option<(Actor, Targeted, &'s module::TargetedDetails, u32)>, StaticsError> {
     let ship = match entity {
         Entity::Facility(_) | Entity::Asteroid(_) => {
             unreachable!("Only ships can use modules {:?}", entity)
@@ -60,11 +65,11 @@ fn apply_targeted_to_origin<'s>(
         .get(module_index as usize)
         .copied()
     {
-        let details = statics.modules_targeted.get(&targeted);
+        let details = statics.modules_targeted.try_get(&targeted)?;
         if let Some(result) = apply_to_origin(ship.collateral, &details.effects_origin) {
             ship.collateral = result;
-            let free_cargo = ship.free_cargo(statics);
-            return Some(((&*entity).into(), targeted, details, free_cargo));
+            let free_cargo = ship.free_cargo(statics)?;
+            return Ok(Some(((&*entity).into(), targeted, details, free_cargo)));
         }
     } else {
         println!(
@@ -72,24 +77,28 @@ fn apply_targeted_to_origin<'s>(
             module_index, ship.fitting
         );
     }
-    None
+    Ok(None)
 }
 
 /// Returns loot
-#[must_use]
+///
+/// `free_cargo` is the free volume of the origin
 fn apply_targeted_to_target(
+    statics: &Statics,
     entity: &mut Entity,
     module: &module::TargetedDetails,
     free_cargo: u32,
-) -> Vec<(Item, u32)> {
+) -> Result<Vec<(Item, u32)>, StaticsError> {
     match entity {
         Entity::Facility(_) => {
             // immune
-            vec![]
+            Ok(vec![])
         }
         Entity::Asteroid(entity) => {
             entity.collateral = apply_to_target(entity.collateral, &module.effects_target);
             let mut loot = Vec::new();
+            let ore = Item::Ore(entity.ore);
+            let fits_in_cargo = free_cargo / statics.items.try_get(&ore)?.volume.max(1);
             let amount_mined = module
                 .effects_target
                 .iter()
@@ -98,15 +107,15 @@ fn apply_targeted_to_target(
                     _ => None,
                 })
                 .unwrap_or_default()
-                .min(free_cargo)
+                .min(fits_in_cargo)
                 .min(entity.remaining_ore);
             entity.remaining_ore -= amount_mined;
-            loot.push((Item::Ore(entity.ore), amount_mined));
-            loot
+            loot.push((ore, amount_mined));
+            Ok(loot)
         }
         Entity::Npc((_, ship)) | Entity::Player((_, ship)) => {
             ship.collateral = apply_to_target(ship.collateral, &module.effects_target);
-            vec![]
+            Ok(vec![])
         }
     }
 }
@@ -118,32 +127,78 @@ pub fn apply_targeted(
     module_index: u8,
     target_index_in_site: u8,
     log: &mut Vec<Log>,
-) {
+) -> Result<(), StaticsError> {
     // First only on origin
 
-    let towards_target = entities
-        .get_mut(&actor)
-        .and_then(|origin| apply_targeted_to_origin(statics, origin, module_index));
+    let towards_target = match entities.get_mut(&actor) {
+        Some(origin) => apply_targeted_to_origin(statics, origin, module_index)?,
+        None => None,
+    };
 
     // Then from origin to target
 
-    let loot = towards_target.and_then(|(origin, targeted, module, free_cargo)| {
-        entities
-            .get_mut(&(target_index_in_site as usize))
-            .map(|target| {
-                log.push(Log::ModuleTargeted((origin, targeted, (&*target).into())));
-                apply_targeted_to_target(target, module, free_cargo)
-            })
-    });
+    let loot = towards_target
+        .and_then(|(origin, targeted, module, free_cargo)| {
+            entities
+                .get_mut(&(target_index_in_site as usize))
+                .map(|target| {
+                    log.push(Log::ModuleTargeted((origin, targeted, (&*target).into())));
+                    apply_targeted_to_target(statics, target, module, free_cargo)
+                })
+        })
+        .transpose()?;
 
     // And back to origin
 
     if let Some(loot) = loot {
-        if let Some(cargo) = entities.get_mut(&actor).and_then(|entity| match entity {
-            Entity::Facility(_) | Entity::Asteroid(_) => None,
-            Entity::Npc((_, ship)) | Entity::Player((_, ship)) => Some(&mut ship.cargo),
-        }) {
-            cargo.append(&mut loot.into());
+        if let Some(origin) = entities.get_mut(&actor) {
+            let actor = Actor::from(&*origin);
+            if let Entity::Npc((_, ship)) | Entity::Player((_, ship)) = origin {
+                for (item, amount) in &loot {
+                    if let Item::Ore(ore) = item {
+                        if *amount > 0 {
+                            log.push(Log::Mined((actor, *ore, *amount)));
+                        }
+                    }
+                }
+                ship.cargo.append(&mut loot.into());
+            }
         }
     }
+
+    Ok(())
+}
+
+#[test]
+fn mining_is_limited_by_cargo_volume() {
+    use crate::fixed::item::Ore;
+    use crate::player::Player;
+    use crate::ship::{Fitting, Ship};
+
+    let statics = Statics::default();
+    let ore_volume = statics.items.get(&Item::Ore(Ore::Aromit)).volume;
+    let mut ship = Ship::new(&statics, Fitting::default()).unwrap();
+    let free_cargo = ship.free_cargo(&statics).unwrap();
+    // Leave space for exactly one ore
+    let fill = (free_cargo - ore_volume) / ore_volume;
+    ship.cargo.saturating_add(Ore::Solmit, fill);
+
+    let mut entities = HashMap::new();
+    entities.insert(0, Entity::Player((Player::Telegram(666), ship)));
+    entities.insert(1, Entity::new_asteroid(Ore::Aromit, 42, 42));
+    let mut log = Vec::new();
+    apply_targeted(&statics, &mut entities, 0, 0, 1, &mut log).unwrap();
+
+    match &entities[&0] {
+        Entity::Player((_, ship)) => assert_eq!(ship.cargo.amount(Ore::Aromit), 1),
+        _ => unreachable!(),
+    }
+    match &entities[&1] {
+        Entity::Asteroid(asteroid) => assert_eq!(asteroid.remaining_ore, 41),
+        _ => unreachable!(),
+    }
+    assert!(matches!(
+        log.last(),
+        Some(Log::Mined((Actor::Player(_), Ore::Aromit, 1)))
+    ));
 }
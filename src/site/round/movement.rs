@@ -1,17 +1,29 @@
 use std::collections::HashMap;
 
 use crate::fixed::solarsystem::Solarsystem;
+use crate::fixed::{Statics, StaticsError};
+use crate::player::location::PlayerLocationWarp;
+use crate::site::{warp_rounds, SitesNearPlanet};
+use crate::universe::Wormhole;
 
 use super::{Entity, Log, Site};
 
+/// Starts the warp towards the target which takes rounds depending on the planets in between.
+/// When the planet of a site is unknown the warp takes a single round.
+#[allow(clippy::too_many_arguments)]
 pub fn warp_out(
+    statics: &Statics,
     solarsystem: Solarsystem,
+    site: Site,
+    sites: &SitesNearPlanet,
     entities: &mut HashMap<usize, Entity>,
     actor: usize,
     target: Site,
-    warping_out: &mut Vec<(Solarsystem, Site, Entity)>,
+    warping_out: &mut Vec<(Entity, PlayerLocationWarp)>,
     log: &mut Vec<Log>,
-) {
+) -> Result<(), StaticsError> {
+    let details = statics.solarsystems.try_get(&solarsystem)?;
+    let rounds = warp_rounds(details, sites, site, target).unwrap_or(1);
     let entity = entities.get(&actor).expect("only existing actors can warp");
     let ship = match entity {
         Entity::Facility(_) | Entity::Asteroid(_) => {
@@ -23,21 +35,31 @@ pub fn warp_out(
         // TODO: capacitor cost
         if let Some(entity) = entities.remove(&actor) {
             log.push(Log::WarpOut((&entity).into()));
-            warping_out.push((solarsystem, target, entity));
+            let warp = PlayerLocationWarp::new(solarsystem, target, rounds);
+            warping_out.push((entity, warp));
         }
     }
+    Ok(())
 }
 
+/// Jumps through the stargate or wormhole of the site.
+/// Wormholes which collapsed or have no jumps left for this round can not be jumped through.
 pub fn jump(
     origin_solarsystem: Solarsystem,
     origin_site: Site,
+    wormholes: &[Wormhole],
     entities: &mut HashMap<usize, Entity>,
     actor: usize,
-    warping_out: &mut Vec<(Solarsystem, Site, Entity)>,
+    warping_out: &mut Vec<(Entity, PlayerLocationWarp)>,
     log: &mut Vec<Log>,
 ) {
-    if let Site::Stargate(target_solarsystem) = origin_site {
-        let can_jump = entities.get(&actor).map_or(false, |entity| match entity {
+    if let Site::Stargate(target_solarsystem) | Site::Wormhole(target_solarsystem) = origin_site {
+        if matches!(origin_site, Site::Wormhole(_))
+            && !wormhole_open(wormholes, origin_solarsystem, target_solarsystem, log)
+        {
+            return;
+        }
+        let can_jump = entities.get(&actor).is_some_and(|entity| match entity {
             Entity::Facility(_) | Entity::Asteroid(_) => {
                 unreachable!("Only ships can jump {:?}", entity)
             }
@@ -46,16 +68,35 @@ pub fn jump(
         if can_jump {
             if let Some(entity) = entities.remove(&actor) {
                 log.push(Log::Jump((&entity).into()));
-                warping_out.push((
-                    target_solarsystem,
-                    Site::Stargate(origin_solarsystem),
-                    entity,
-                ));
+                // Arrive at the other end of the same kind of connection
+                let arrival = match origin_site {
+                    Site::Wormhole(_) => Site::Wormhole(origin_solarsystem),
+                    _ => Site::Stargate(origin_solarsystem),
+                };
+                // Jumping takes no time
+                let warp = PlayerLocationWarp::new(target_solarsystem, arrival, 0);
+                warping_out.push((entity, warp));
             }
         }
     }
 }
 
+/// Every jump of this round already used up one of the remaining jumps of the wormhole
+fn wormhole_open(
+    wormholes: &[Wormhole],
+    origin: Solarsystem,
+    target: Solarsystem,
+    log: &[Log],
+) -> bool {
+    let jumped = log.iter().filter(|o| matches!(o, Log::Jump(_))).count();
+    wormholes.iter().any(|wormhole| {
+        wormhole.connects(origin, target)
+            && !wormhole.is_collapsed()
+            && usize::try_from(wormhole.remaining_jumps)
+                .map_or(true, |remaining| remaining > jumped)
+    })
+}
+
 pub fn dock(
     solarsystem: Solarsystem,
     site: Site,
@@ -65,7 +106,7 @@ pub fn dock(
     log: &mut Vec<Log>,
 ) {
     if let Site::Station(station) = site {
-        let can_dock = entities.get(&actor).map_or(false, |entity| match entity {
+        let can_dock = entities.get(&actor).is_some_and(|entity| match entity {
             Entity::Facility(_) | Entity::Asteroid(_) => {
                 unreachable!("Only ships can jump {:?}", entity)
             }
@@ -79,3 +120,130 @@ pub fn dock(
         }
     }
 }
+
+#[cfg(test)]
+fn wormhole(rounds: u32, jumps: u32) -> Wormhole {
+    use crate::universe::WormholeEnd;
+    Wormhole::new(
+        WormholeEnd {
+            solarsystem: Solarsystem::Wabinihwa,
+            planet: 1,
+        },
+        WormholeEnd {
+            solarsystem: Solarsystem::Vosu,
+            planet: 2,
+        },
+        rounds,
+        jumps,
+    )
+}
+
+#[test]
+fn jump_through_wormhole_arrives_at_wormhole() {
+    let player = Entity::Player((
+        crate::player::Player::Telegram(666),
+        crate::ship::Ship::default(),
+    ));
+    let mut entities = HashMap::new();
+    entities.insert(0, player.clone());
+    let mut warping_out = Vec::new();
+    let mut log = Vec::new();
+    jump(
+        Solarsystem::Vosu,
+        Site::Wormhole(Solarsystem::Wabinihwa),
+        &[wormhole(10, 10)],
+        &mut entities,
+        0,
+        &mut warping_out,
+        &mut log,
+    );
+    assert!(entities.is_empty());
+    assert_eq!(
+        warping_out,
+        [(
+            player,
+            PlayerLocationWarp::new(Solarsystem::Wabinihwa, Site::Wormhole(Solarsystem::Vosu), 0)
+        )]
+    );
+    assert_eq!(log.len(), 1);
+}
+
+#[test]
+fn jump_through_stargate_arrives_at_stargate() {
+    let player = Entity::Player((
+        crate::player::Player::Telegram(666),
+        crate::ship::Ship::default(),
+    ));
+    let mut entities = HashMap::new();
+    entities.insert(0, player.clone());
+    let mut warping_out = Vec::new();
+    jump(
+        Solarsystem::Plagar,
+        Site::Stargate(Solarsystem::Vosu),
+        &[],
+        &mut entities,
+        0,
+        &mut warping_out,
+        &mut Vec::new(),
+    );
+    assert_eq!(
+        warping_out,
+        [(
+            player,
+            PlayerLocationWarp::new(Solarsystem::Vosu, Site::Stargate(Solarsystem::Plagar), 0)
+        )]
+    );
+}
+
+#[test]
+fn jump_through_collapsed_wormhole_stays() {
+    let player = Entity::Player((
+        crate::player::Player::Telegram(666),
+        crate::ship::Ship::default(),
+    ));
+    for wormholes in [vec![], vec![wormhole(0, 10)], vec![wormhole(10, 0)]] {
+        let mut entities = HashMap::new();
+        entities.insert(0, player.clone());
+        let mut warping_out = Vec::new();
+        let mut log = Vec::new();
+        jump(
+            Solarsystem::Vosu,
+            Site::Wormhole(Solarsystem::Wabinihwa),
+            &wormholes,
+            &mut entities,
+            0,
+            &mut warping_out,
+            &mut log,
+        );
+        assert_eq!(entities.len(), 1);
+        assert!(warping_out.is_empty());
+        assert!(log.is_empty());
+    }
+}
+
+#[test]
+fn jump_through_wormhole_uses_up_jumps_within_round() {
+    let player = Entity::Player((
+        crate::player::Player::Telegram(666),
+        crate::ship::Ship::default(),
+    ));
+    let mut entities = HashMap::new();
+    entities.insert(0, player.clone());
+    entities.insert(1, player);
+    let mut warping_out = Vec::new();
+    let mut log = Vec::new();
+    for actor in [0, 1] {
+        jump(
+            Solarsystem::Vosu,
+            Site::Wormhole(Solarsystem::Wabinihwa),
+            &[wormhole(10, 1)],
+            &mut entities,
+            actor,
+            &mut warping_out,
+            &mut log,
+        );
+    }
+    assert_eq!(entities.len(), 1);
+    assert_eq!(warping_out.len(), 1);
+    assert_eq!(log.len(), 1);
+}
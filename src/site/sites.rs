@@ -3,12 +3,13 @@ use std::collections::BTreeMap;
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
-use crate::fixed::solarsystem::Solarsystem;
+use crate::fixed::solarsystem::{self, Solarsystem};
 
 // TODO: move to crate::site
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase", tag = "kind", content = "unique")]
 pub enum Site {
     /// Zero-based index of station. Station I is 0, station IV is 3.
@@ -17,11 +18,14 @@ pub enum Site {
     Stargate(Solarsystem),
     /// unique number, maybe random
     AsteroidField(u8),
+    /// Temporary connection to the target solarsystem
+    Wormhole(Solarsystem),
 }
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SitesNearPlanet(BTreeMap<u8, Vec<Site>>);
 
 impl std::str::FromStr for Site {
@@ -37,17 +41,19 @@ impl std::str::FromStr for Site {
             "station" => Ok(Self::Station(unique.parse()?)),
             "stargate" => Ok(Self::Stargate(unique.parse()?)),
             "asteroidField" => Ok(Self::AsteroidField(unique.parse()?)),
+            "wormhole" => Ok(Self::Wormhole(unique.parse()?)),
             _ => Err(anyhow!("unknown site kind {} {}", kind, s)),
         }
     }
 }
 
-impl ToString for Site {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Site {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Station(index) => format!("station-{}", index),
-            Self::Stargate(target) => format!("stargate-{}", target),
-            Self::AsteroidField(unique) => format!("asteroidField-{:03}", unique),
+            Self::Station(index) => write!(f, "station-{}", index),
+            Self::Stargate(target) => write!(f, "stargate-{}", target),
+            Self::AsteroidField(unique) => write!(f, "asteroidField-{:03}", unique),
+            Self::Wormhole(target) => write!(f, "wormhole-{}", target),
         }
     }
 }
@@ -71,6 +77,48 @@ impl SitesNearPlanet {
             }
         }
     }
+
+    /// The planet the site is near to
+    #[must_use]
+    pub fn planet(&self, site: Site) -> Option<u8> {
+        self.0
+            .iter()
+            .find(|(_, sites)| sites.contains(&site))
+            .map(|(planet, _)| *planet)
+    }
+}
+
+/// The planet the site is near to.
+///
+/// Stations and stargates are looked up in the statics, other sites in the `SitesNearPlanet`.
+#[must_use]
+pub fn planet_of(
+    details: &solarsystem::Details,
+    sites: &SitesNearPlanet,
+    site: Site,
+) -> Option<u8> {
+    match site {
+        Site::Station(index) => details.stations.get(index as usize).copied(),
+        Site::Stargate(target) => details.stargates.get(&target).copied(),
+        Site::AsteroidField(_) | Site::Wormhole(_) => None,
+    }
+    .or_else(|| sites.planet(site))
+}
+
+/// Rounds it takes to warp from one site to another within the same solarsystem.
+///
+/// Every planet in between takes one round, but a warp takes at least one round.
+/// Returns None when the planet of one of the sites is unknown.
+#[must_use]
+pub fn warp_rounds(
+    details: &solarsystem::Details,
+    sites: &SitesNearPlanet,
+    from: Site,
+    to: Site,
+) -> Option<u8> {
+    let from = planet_of(details, sites, from)?;
+    let to = planet_of(details, sites, to)?;
+    Some(from.abs_diff(to).max(1))
 }
 
 #[test]
@@ -91,6 +139,39 @@ fn can_serde_parse_asteroid_field() {
     crate::test_helper::can_serde_parse(&data);
 }
 
+#[test]
+fn can_serde_parse_wormhole() {
+    let data = Site::Wormhole(Solarsystem::Vosu);
+    crate::test_helper::can_serde_parse(&data);
+}
+
+#[test]
+fn warp_rounds_depend_on_planet_distance() {
+    let statics = crate::fixed::Statics::default();
+    let details = statics.solarsystems.get(&Solarsystem::Wabinihwa);
+    let mut sites = SitesNearPlanet::default();
+    sites.add(6, Site::AsteroidField(1));
+
+    // Station 0 and 2 are both at planet 2
+    let same_planet = warp_rounds(details, &sites, Site::Station(0), Site::Station(2));
+    assert_eq!(same_planet, Some(1));
+
+    // Station 0 at planet 2, Stargate to Arama at planet 4
+    let stargate = warp_rounds(
+        details,
+        &sites,
+        Site::Station(0),
+        Site::Stargate(Solarsystem::Arama),
+    );
+    assert_eq!(stargate, Some(2));
+
+    let field = warp_rounds(details, &sites, Site::AsteroidField(1), Site::Station(0));
+    assert_eq!(field, Some(4));
+
+    let unknown = warp_rounds(details, &sites, Site::AsteroidField(2), Site::Station(0));
+    assert_eq!(unknown, None);
+}
+
 #[test]
 fn can_string_parse_station() {
     let data = Site::Station(2);
@@ -108,3 +189,9 @@ fn can_string_parse_asteroid_field() {
     let data = Site::AsteroidField(42);
     crate::test_helper::can_string_parse(&data);
 }
+
+#[test]
+fn can_string_parse_wormhole() {
+    let data = Site::Wormhole(Solarsystem::Vosu);
+    crate::test_helper::can_string_parse(&data);
+}
@@ -1,15 +1,17 @@
 #![allow(clippy::module_name_repetitions)]
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::fixed::item::Item;
 use crate::fixed::module::Module;
 use crate::fixed::solarsystem::Solarsystem;
-use crate::market::{Order, Trader};
+use crate::market::{Order, OrderId, Trader};
 use crate::player::Player;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(
     rename_all = "camelCase",
     rename = "StationInstruction",
@@ -34,6 +36,20 @@ pub enum Instruction {
     // Unrelated from ships
     Buy(PlaceOrder),
     Sell(PlaceOrder),
+    /// Removes an own order from the market of the item
+    CancelOrder {
+        item: Item,
+        #[cfg_attr(feature = "ts-rs", ts(type = "number"))]
+        order: OrderId,
+    },
+    /// Changes amount and price of an own order
+    ModifyOrder {
+        item: Item,
+        #[cfg_attr(feature = "ts-rs", ts(type = "number"))]
+        order: OrderId,
+        amount: u32,
+        paperclips: u64,
+    },
     Recycle {
         item: Item,
         amount: u32,
@@ -42,15 +58,24 @@ pub enum Instruction {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PlaceOrder {
     pub item: Item,
     pub amount: u32,
     pub paperclips: u64,
+    /// Removes the order from the market at that time when not fulfilled until then
+    #[cfg_attr(
+        feature = "ts-rs",
+        ts(type = "`${number}-${number}-${number}T${number}:${number}:${number}${string}Z`")
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransferItems {
     pub item: Item,
@@ -58,15 +83,17 @@ pub struct TransferItems {
 }
 
 impl PlaceOrder {
+    /// Nothing is reserved for the order yet, see `market::escrow::place`.
     #[must_use]
     pub fn to_order(&self, player: Player, solarsystem: Solarsystem, station: u8) -> (Item, Order) {
-        let order = Order::new_now(
+        let mut order = Order::new_now(
             solarsystem,
             station,
             Trader::Player(player),
             self.amount,
             self.paperclips,
         );
+        order.expires = self.expires;
         (self.item, order)
     }
 }
@@ -111,10 +138,61 @@ fn can_parse_buy() {
         item: Item::EXAMPLE,
         amount: 42,
         paperclips: 666,
+        expires: None,
     });
     crate::test_helper::can_serde_parse(&data);
 }
 
+#[test]
+fn can_parse_sell_with_expiry() {
+    let data = Instruction::Sell(PlaceOrder {
+        item: Item::EXAMPLE,
+        amount: 42,
+        paperclips: 666,
+        expires: Some(
+            DateTime::parse_from_rfc3339("1996-12-19T16:57:12Z")
+                .unwrap()
+                .into(),
+        ),
+    });
+    crate::test_helper::can_serde_parse(&data);
+}
+
+#[test]
+fn can_parse_cancel_order() {
+    let data = Instruction::CancelOrder {
+        item: Item::EXAMPLE,
+        order: 1337,
+    };
+    crate::test_helper::can_serde_parse(&data);
+}
+
+#[test]
+fn can_parse_modify_order() {
+    let data = Instruction::ModifyOrder {
+        item: Item::EXAMPLE,
+        order: 1337,
+        amount: 42,
+        paperclips: 666,
+    };
+    crate::test_helper::can_serde_parse(&data);
+}
+
+#[test]
+fn place_order_keeps_expiry() {
+    let expires = Utc::now();
+    let place = PlaceOrder {
+        item: Item::EXAMPLE,
+        amount: 42,
+        paperclips: 666,
+        expires: Some(expires),
+    };
+    let (item, order) = place.to_order(Player::Telegram(666), Solarsystem::default(), 0);
+    assert_eq!(item, Item::EXAMPLE);
+    assert_eq!(order.expires, Some(expires));
+    assert_ne!(order.id, 0);
+}
+
 #[test]
 fn can_parse_recycle() {
     let data = Instruction::Recycle {
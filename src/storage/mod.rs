@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
 
 use crate::fixed::item::Item;
+use crate::fixed::{Statics, StaticsError};
 
 type Amount = u32;
 
@@ -10,6 +11,7 @@ type Amount = u32;
 
 #[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Storage(HashMap<Item, Amount>);
 
 impl Serialize for Storage {
@@ -71,15 +73,18 @@ impl Storage {
         true
     }
 
-    /// Total slots used by the stored goods.
-    /// Will probably be replaced in the future with a `total_volume` or something like that.
-    #[must_use]
-    pub fn total_slots(&self) -> Amount {
-        let mut total: Amount = 0;
-        for amount in self.0.values() {
-            total = amount.saturating_add(total);
+    /// Total volume used by the stored goods.
+    /// # Errors
+    /// Errors when a stored item is not part of the statics
+    pub fn total_volume(&self, statics: &Statics) -> Result<u32, StaticsError> {
+        let mut total: u32 = 0;
+        for (item, amount) in &self.0 {
+            if *amount > 0 {
+                let volume = statics.items.try_get(item)?.volume;
+                total = total.saturating_add(volume.saturating_mul(*amount));
+            }
         }
-        total
+        Ok(total)
     }
 
     #[must_use]
@@ -192,6 +197,22 @@ fn can_add() {
     assert_eq!(data, expected);
 }
 
+#[test]
+fn total_volume_respects_item_volume() {
+    use crate::fixed::item::Ore;
+    use crate::fixed::module::Targeted;
+    let statics = Statics::default();
+    let data: Storage = vec![
+        (Targeted::RookieLaser.into(), 2),
+        (Item::Ore(Ore::Aromit), 12),
+        (Item::Ore(Ore::Solmit), 0),
+    ]
+    .into();
+    let laser = statics.items.get(&Targeted::RookieLaser.into()).volume;
+    let ore = statics.items.get(&Item::Ore(Ore::Aromit)).volume;
+    assert_eq!(data.total_volume(&statics), Ok(laser * 2 + ore * 12));
+}
+
 #[test]
 fn take_exact_works() {
     use crate::fixed::item::Ore;
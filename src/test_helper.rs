@@ -39,3 +39,22 @@ where
     let json = serde_json::to_string_pretty(&input).unwrap();
     serde_json::from_str(&json).unwrap()
 }
+
+/// Creates a new empty directory which is not shared with other tests or concurrent test runs
+pub fn unique_temp_dir(name: &str) -> std::path::PathBuf {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "space-game-typings-{}-{}-{}-{}",
+        name,
+        std::process::id(),
+        nanos,
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
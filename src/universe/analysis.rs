@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::fixed::solarsystem::Solarsystem;
+
+use super::Universe;
+
+/// Structural overview of the stargate network.
+/// Helpful to review changes of the map.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Analysis {
+    /// Solarsystems which split the map into multiple parts when removed
+    pub chokepoints: Vec<Solarsystem>,
+    /// Solarsystems with only a single neighbor
+    pub dead_ends: Vec<Solarsystem>,
+    /// Groups of solarsystems connected with each other, ignoring the direction of stargates.
+    /// A map where everything is reachable has exactly one.
+    pub components: Vec<Vec<Solarsystem>>,
+    /// Stargates without a stargate back
+    pub one_directional_stargates: Vec<(Solarsystem, Solarsystem)>,
+    /// Most jumps needed on the shortest route between any two solarsystems which can reach each other
+    pub diameter: usize,
+}
+
+impl Universe {
+    #[must_use]
+    pub fn analyze(&self) -> Analysis {
+        let undirected = self.undirected();
+        Analysis {
+            chokepoints: articulation_points(&undirected),
+            dead_ends: undirected
+                .iter()
+                .filter(|(_, neighbors)| neighbors.len() == 1)
+                .map(|(solarsystem, _)| *solarsystem)
+                .collect(),
+            components: components(&undirected),
+            one_directional_stargates: self.one_directional_stargates(),
+            diameter: self.diameter(),
+        }
+    }
+
+    fn undirected(&self) -> BTreeMap<Solarsystem, BTreeSet<Solarsystem>> {
+        let mut result: BTreeMap<Solarsystem, BTreeSet<Solarsystem>> = self
+            .solarsystems()
+            .map(|solarsystem| (solarsystem, BTreeSet::new()))
+            .collect();
+        for (from, targets) in &self.connections {
+            for to in targets {
+                result.entry(*from).or_default().insert(*to);
+                result.entry(*to).or_default().insert(*from);
+            }
+        }
+        result
+    }
+
+    fn one_directional_stargates(&self) -> Vec<(Solarsystem, Solarsystem)> {
+        let mut result = Vec::new();
+        for (from, targets) in &self.connections {
+            for to in targets {
+                let back = self.connections.get(to).is_some_and(|o| o.contains(from));
+                if !back {
+                    result.push((*from, *to));
+                }
+            }
+        }
+        result
+    }
+
+    fn diameter(&self) -> usize {
+        self.solarsystems()
+            .filter_map(|from| self.within_jumps(from, usize::MAX).into_values().max())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+fn components(graph: &BTreeMap<Solarsystem, BTreeSet<Solarsystem>>) -> Vec<Vec<Solarsystem>> {
+    let mut visited = BTreeSet::new();
+    let mut result = Vec::new();
+    for start in graph.keys() {
+        if !visited.insert(*start) {
+            continue;
+        }
+        let mut component = vec![*start];
+        let mut stack = vec![*start];
+        while let Some(current) = stack.pop() {
+            for neighbor in &graph[&current] {
+                if visited.insert(*neighbor) {
+                    component.push(*neighbor);
+                    stack.push(*neighbor);
+                }
+            }
+        }
+        component.sort();
+        result.push(component);
+    }
+    result
+}
+
+/// Tarjan: a solarsystem is an articulation point when one of its subtrees can not reach above it.
+///
+/// The depth first search keeps its own stack as the graph can be deeper than the call stack allows.
+fn articulation_points<K: Copy + Ord>(graph: &BTreeMap<K, BTreeSet<K>>) -> Vec<K> {
+    struct Frame<K> {
+        current: K,
+        parent: Option<K>,
+        neighbors: Vec<K>,
+        next: usize,
+        children: usize,
+    }
+
+    struct State<K> {
+        discovered: BTreeMap<K, usize>,
+        low: BTreeMap<K, usize>,
+        result: BTreeSet<K>,
+    }
+
+    impl<K: Copy + Ord> State<K> {
+        fn discover(
+            &mut self,
+            graph: &BTreeMap<K, BTreeSet<K>>,
+            current: K,
+            parent: Option<K>,
+        ) -> Frame<K> {
+            let time = self.discovered.len();
+            self.discovered.insert(current, time);
+            self.low.insert(current, time);
+            Frame {
+                current,
+                parent,
+                neighbors: graph[&current].iter().copied().collect(),
+                next: 0,
+                children: 0,
+            }
+        }
+
+        fn lower(&mut self, node: K, value: usize) {
+            let low = self.low[&node].min(value);
+            self.low.insert(node, low);
+        }
+    }
+
+    let mut state = State {
+        discovered: BTreeMap::new(),
+        low: BTreeMap::new(),
+        result: BTreeSet::new(),
+    };
+    for start in graph.keys() {
+        if state.discovered.contains_key(start) {
+            continue;
+        }
+
+        let mut stack = vec![state.discover(graph, *start, None)];
+        while let Some(frame) = stack.last_mut() {
+            if let Some(neighbor) = frame.neighbors.get(frame.next).copied() {
+                frame.next += 1;
+                if Some(neighbor) == frame.parent {
+                    continue;
+                }
+                if let Some(discovered) = state.discovered.get(&neighbor).copied() {
+                    let current = frame.current;
+                    state.lower(current, discovered);
+                    continue;
+                }
+
+                frame.children += 1;
+                let current = frame.current;
+                stack.push(state.discover(graph, neighbor, Some(current)));
+                continue;
+            }
+
+            // All neighbors are visited, hand the result to the parent
+            let (current, parent, children) = (frame.current, frame.parent, frame.children);
+            stack.pop();
+            if let Some(parent) = parent {
+                let low = state.low[&current];
+                state.lower(parent, low);
+                let parent_is_root = stack.last().is_none_or(|frame| frame.parent.is_none());
+                if !parent_is_root && low >= state.discovered[&parent] {
+                    state.result.insert(parent);
+                }
+            } else if children > 1 {
+                state.result.insert(current);
+            }
+        }
+    }
+    state.result.into_iter().collect()
+}
+
+#[test]
+fn analyze_default_map() {
+    let analysis = super::example().analyze();
+    assert_eq!(
+        analysis.chokepoints,
+        [Solarsystem::Arama, Solarsystem::Plagar]
+    );
+    assert_eq!(analysis.dead_ends, [Solarsystem::Vosu]);
+    assert_eq!(analysis.components.len(), 1);
+    assert_eq!(analysis.components[0].len(), 6);
+    assert!(analysis.one_directional_stargates.is_empty());
+    assert_eq!(
+        analysis.diameter,
+        super::example()
+            .jump_distance(Solarsystem::Liagi, Solarsystem::Vosu)
+            .unwrap()
+    );
+}
+
+#[test]
+fn analyze_finds_one_directional_and_components() {
+    let statics = crate::fixed::Statics::default();
+    let mut solarsystems = statics.solarsystems;
+    solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap()
+        .stargates
+        .clear();
+    solarsystems
+        .data
+        .get_mut(&Solarsystem::Wabinihwa)
+        .unwrap()
+        .stargates
+        .remove(&Solarsystem::Liagi);
+    let analysis = Universe::from(&solarsystems).analyze();
+    assert_eq!(
+        analysis.one_directional_stargates,
+        [
+            (Solarsystem::Liagi, Solarsystem::Wabinihwa),
+            (Solarsystem::Plagar, Solarsystem::Vosu),
+        ]
+    );
+    assert_eq!(analysis.components.len(), 1);
+
+    solarsystems
+        .data
+        .get_mut(&Solarsystem::Plagar)
+        .unwrap()
+        .stargates
+        .remove(&Solarsystem::Vosu);
+    let analysis = Universe::from(&solarsystems).analyze();
+    assert_eq!(analysis.components.len(), 2);
+    assert_eq!(analysis.components[1], [Solarsystem::Vosu]);
+    assert_eq!(analysis.dead_ends, [Solarsystem::Plagar]);
+}
+
+#[test]
+fn chokepoints_of_a_line() {
+    let line = [
+        Solarsystem::Wabinihwa,
+        Solarsystem::Arama,
+        Solarsystem::Iramil,
+        Solarsystem::Liagi,
+    ];
+    let mut universe = Universe::default();
+    for pair in line.windows(2) {
+        universe
+            .connections
+            .entry(pair[0])
+            .or_default()
+            .insert(pair[1]);
+        universe
+            .connections
+            .entry(pair[1])
+            .or_default()
+            .insert(pair[0]);
+    }
+    let analysis = universe.analyze();
+    assert_eq!(
+        analysis.chokepoints,
+        [Solarsystem::Arama, Solarsystem::Iramil]
+    );
+    assert_eq!(
+        analysis.dead_ends,
+        [Solarsystem::Wabinihwa, Solarsystem::Liagi]
+    );
+    assert_eq!(analysis.diameter, 3);
+}
+
+#[test]
+fn chokepoints_of_a_deep_graph() {
+    let length = 100_000_u32;
+    let mut graph = BTreeMap::<u32, BTreeSet<u32>>::new();
+    for i in 0..length {
+        let neighbors = graph.entry(i).or_default();
+        if i > 0 {
+            neighbors.insert(i - 1);
+        }
+        if i + 1 < length {
+            neighbors.insert(i + 1);
+        }
+    }
+    let points = articulation_points(&graph);
+    assert_eq!(points, (1..length - 1).collect::<Vec<_>>());
+}
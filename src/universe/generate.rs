@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::random::Rng;
+
+const SYLLABLES: &[&str] = &[
+    "a", "ar", "ba", "dor", "el", "gar", "gi", "hwa", "ir", "ko", "li", "ma", "mil", "ni", "pla",
+    "ra", "su", "ta", "vo", "zen",
+];
+
+/// What the generated solarsystems have to look like
+#[derive(Debug, Clone)]
+pub struct Constraints {
+    /// Amount of solarsystems. Ignored when `names` are given.
+    pub amount: usize,
+    /// Names of the solarsystems. The first one is the home system.
+    /// Names are generated when empty.
+    pub names: Vec<String>,
+    /// Maximum amount of stargates a single solarsystem can have. Has to be at least 2.
+    pub max_stargates: usize,
+    pub max_planets: u8,
+    pub max_stations: u8,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            amount: 20,
+            names: Vec::new(),
+            max_stargates: 4,
+            max_planets: 8,
+            max_stations: 3,
+        }
+    }
+}
+
+/// Same structure as a `solarsystem::Details` but not limited to the known solarsystems
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedSolarsystem {
+    pub security: u8,
+    pub planets: u8,
+    pub stargates: BTreeMap<String, u8>,
+    pub stations: Vec<u8>,
+}
+
+/// Generated solarsystems by their name.
+/// Serializes into the format of the `solarsystem.yaml`.
+pub type Generated = BTreeMap<String, GeneratedSolarsystem>;
+
+/// Attempts per requested name before giving up on finding more unique ones
+const NAME_ATTEMPTS: usize = 100;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Only a limited amount of unique names can be built from the syllables.
+    /// Pass `Constraints::names` for bigger universes.
+    NotEnoughNames { requested: usize, found: usize },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughNames { requested, found } => write!(
+                f,
+                "Requested {} solarsystems but only found {} unique names.",
+                requested, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generates a connected universe with symmetric stargates.
+///
+/// The security is highest around the home system and drops with the amount of jumps away from it.
+/// Keep in mind that the statics only know the names of `Solarsystem`.
+/// In order to pass the statics validation the names have to be these with `Solarsystem::default()` as the home system.
+/// # Errors
+/// Errors when not enough unique names can be generated for the requested amount
+pub fn generate(constraints: &Constraints, seed: u64) -> Result<Generated, Error> {
+    let mut rng = Rng::new(seed);
+    let names = if constraints.names.is_empty() {
+        generate_names(&mut rng, constraints.amount)?
+    } else {
+        constraints.names.clone()
+    };
+    let amount = names.len();
+    let max_stargates = constraints.max_stargates.max(2);
+
+    // Spanning tree first to ensure everything is connected.
+    // The most recently added system only has a single stargate so there is always a candidate.
+    let mut connections = vec![BTreeSet::new(); amount];
+    for i in 1..amount {
+        let candidates = (0..i)
+            .filter(|o| connections[*o].len() < max_stargates)
+            .collect::<Vec<_>>();
+        let other = candidates[rng.index(candidates.len())];
+        connections[i].insert(other);
+        connections[other].insert(i);
+    }
+    // Some more stargates to have alternative routes
+    for _ in 0..amount / 2 {
+        let a = rng.index(amount);
+        let b = rng.index(amount);
+        if a != b && connections[a].len() < max_stargates && connections[b].len() < max_stargates {
+            connections[a].insert(b);
+            connections[b].insert(a);
+        }
+    }
+
+    let distances = distances_from_home(&connections);
+    let max_distance = distances.iter().copied().max().unwrap_or_default();
+
+    let mut result = Generated::new();
+    for (i, name) in names.iter().enumerate() {
+        let falloff = u32::try_from(distances[i] * 100 / (max_distance + 1)).unwrap_or(100);
+        let security = 100_u32.saturating_sub(falloff.saturating_add(rng.below(10)));
+        let security = u8::try_from(security).unwrap_or_default();
+
+        let planets = 1 + rng.below_u8(constraints.max_planets.max(1));
+        let min_stations = u32::from(i == 0);
+        let station_amount =
+            min_stations + rng.below(u32::from(constraints.max_stations) + 1 - min_stations);
+        let stations = (0..station_amount)
+            .map(|_| 1 + rng.below_u8(planets))
+            .collect();
+        let stargates = connections[i]
+            .iter()
+            .map(|o| (names[*o].clone(), 1 + rng.below_u8(planets)))
+            .collect();
+
+        result.insert(
+            name.clone(),
+            GeneratedSolarsystem {
+                security,
+                planets,
+                stargates,
+                stations,
+            },
+        );
+    }
+    Ok(result)
+}
+
+/// Writes the generated solarsystems as `solarsystem.yaml` into the given directory.
+///
+/// Only the solarsystem data is written, so the directory is not a complete statics directory.
+/// It can not be used as an overlay either as overlays keep existing stargates which breaks their symmetry.
+/// The file is meant to be inspected or to replace `static/solarsystem.yaml` by hand when all the names are known `Solarsystem`s.
+/// # Errors
+/// Errors when the file couldnt be written
+pub fn write_yaml<P: AsRef<Path>>(generated: &Generated, dir: P) -> anyhow::Result<()> {
+    let yaml = serde_yaml::to_string(generated)?;
+    std::fs::write(dir.as_ref().join("solarsystem.yaml"), yaml)?;
+    Ok(())
+}
+
+/// Different syllables can result in the same name ("a" + "ra" and "ar" + "a")
+/// so the amount of unique names is not known upfront.
+fn generate_names(rng: &mut Rng, amount: usize) -> Result<Vec<String>, Error> {
+    let mut names = BTreeSet::new();
+    let mut ordered = Vec::with_capacity(amount);
+    for _ in 0..amount.saturating_mul(NAME_ATTEMPTS) {
+        if ordered.len() >= amount {
+            break;
+        }
+        let syllables = 2 + rng.below(2);
+        let mut name = (0..syllables)
+            .map(|_| SYLLABLES[rng.index(SYLLABLES.len())])
+            .collect::<String>();
+        name[..1].make_ascii_uppercase();
+        if names.insert(name.clone()) {
+            ordered.push(name);
+        }
+    }
+    if ordered.len() < amount {
+        return Err(Error::NotEnoughNames {
+            requested: amount,
+            found: ordered.len(),
+        });
+    }
+    Ok(ordered)
+}
+
+fn distances_from_home(connections: &[BTreeSet<usize>]) -> Vec<usize> {
+    let mut distances = vec![usize::MAX; connections.len()];
+    let mut queue = VecDeque::new();
+    if !connections.is_empty() {
+        distances[0] = 0;
+        queue.push_back(0);
+    }
+    while let Some(current) = queue.pop_front() {
+        for neighbor in &connections[current] {
+            if distances[*neighbor] == usize::MAX {
+                distances[*neighbor] = distances[current] + 1;
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+    distances
+}
+
+#[test]
+fn same_seed_same_universe() {
+    let constraints = Constraints::default();
+    assert_eq!(generate(&constraints, 42), generate(&constraints, 42));
+    assert_ne!(generate(&constraints, 42), generate(&constraints, 1337));
+}
+
+#[test]
+fn too_many_names_is_an_error() {
+    let constraints = Constraints {
+        amount: 10_000,
+        ..Constraints::default()
+    };
+    let result = generate(&constraints, 42);
+    assert!(matches!(
+        result,
+        Err(Error::NotEnoughNames {
+            requested: 10_000,
+            found
+        }) if found < 10_000
+    ));
+}
+
+#[test]
+fn respects_constraints() {
+    let constraints = Constraints {
+        amount: 30,
+        max_stargates: 3,
+        ..Constraints::default()
+    };
+    for seed in 0..20 {
+        let generated = generate(&constraints, seed).unwrap();
+        assert_eq!(generated.len(), 30);
+        for (name, details) in &generated {
+            assert!(details.security <= 100);
+            assert!(details.stargates.len() <= 3);
+            assert!(!details.stargates.is_empty(), "{} has no stargates", name);
+            assert!(details.planets >= 1 && details.planets <= constraints.max_planets);
+            for (target, planet) in &details.stargates {
+                assert!(*planet >= 1 && *planet <= details.planets);
+                assert!(generated[target].stargates.contains_key(name));
+            }
+            for planet in &details.stations {
+                assert!(*planet >= 1 && *planet <= details.planets);
+            }
+        }
+    }
+}
+
+#[test]
+fn high_security_around_home() {
+    let constraints = Constraints {
+        names: (0..15).map(|i| format!("System{:02}", i)).collect(),
+        ..Constraints::default()
+    };
+    let generated = generate(&constraints, 42).unwrap();
+    let home = &generated["System00"];
+    assert!(home.security >= 90);
+    assert!(!home.stations.is_empty());
+    let lowest = generated.values().map(|o| o.security).min().unwrap();
+    assert!(lowest < 50);
+}
+
+#[test]
+fn passes_statics_validation() -> anyhow::Result<()> {
+    use crate::fixed::solarsystem::Solarsystem;
+    let constraints = Constraints {
+        names: [
+            Solarsystem::Wabinihwa,
+            Solarsystem::Arama,
+            Solarsystem::Iramil,
+            Solarsystem::Liagi,
+            Solarsystem::Plagar,
+            Solarsystem::Vosu,
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+        ..Constraints::default()
+    };
+    let dir = crate::test_helper::unique_temp_dir("generate-test");
+    for seed in 0..10 {
+        write_yaml(&generate(&constraints, seed)?, &dir)?;
+        let yaml = std::fs::read_to_string(dir.join("solarsystem.yaml"))?;
+        let mut statics = crate::fixed::Statics::default();
+        statics.solarsystems.data = serde_yaml::from_str(&yaml)?;
+        assert_eq!(statics.validate(), vec![], "seed {}", seed);
+    }
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
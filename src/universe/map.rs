@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::fixed::solarsystem::Solarsystem;
+use crate::fixed::Solarsystems;
+
+use super::Universe;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 600.0;
+const MARGIN: f64 = 80.0;
+const ITERATIONS: usize = 300;
+
+/// Position of a solarsystem on the map
+pub type Point = (f64, f64);
+
+/// Places the solarsystems on a 800 x 600 plane.
+///
+/// Uses a force-directed layout: every solarsystem repels each other while stargates pull them together.
+/// The result is deterministic.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn layout(universe: &Universe) -> BTreeMap<Solarsystem, Point> {
+    let solarsystems = universe.solarsystems().collect::<Vec<_>>();
+    let amount = solarsystems.len();
+    if amount == 0 {
+        return BTreeMap::new();
+    }
+
+    let index = solarsystems
+        .iter()
+        .enumerate()
+        .map(|(i, o)| (*o, i))
+        .collect::<BTreeMap<_, _>>();
+    let mut edges = Vec::new();
+    for (a, solarsystem) in solarsystems.iter().enumerate() {
+        for target in universe.neighbors(*solarsystem) {
+            if let Some(b) = index.get(&target).copied() {
+                if a < b || !universe.neighbors(target).contains(solarsystem) {
+                    edges.push((a, b));
+                }
+            }
+        }
+    }
+
+    // Start on a circle
+    let radius = WIDTH.min(HEIGHT) / 3.0;
+    let mut positions = (0..amount)
+        .map(|i| {
+            let angle = std::f64::consts::TAU * (i as f64) / (amount as f64);
+            (
+                WIDTH / 2.0 + radius * angle.cos(),
+                HEIGHT / 2.0 + radius * angle.sin(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let k = (WIDTH * HEIGHT / amount as f64).sqrt();
+    let start_temperature = WIDTH / 10.0;
+    for iteration in 0..ITERATIONS {
+        let temperature = start_temperature * (1.0 - iteration as f64 / ITERATIONS as f64);
+        let mut displacement = vec![(0.0, 0.0); amount];
+
+        for a in 0..amount {
+            for b in (a + 1)..amount {
+                let (dx, dy, distance) = delta(positions[a], positions[b]);
+                let force = k * k / distance;
+                displacement[a].0 += dx / distance * force;
+                displacement[a].1 += dy / distance * force;
+                displacement[b].0 -= dx / distance * force;
+                displacement[b].1 -= dy / distance * force;
+            }
+        }
+        for (a, b) in &edges {
+            let (dx, dy, distance) = delta(positions[*a], positions[*b]);
+            let force = distance * distance / k;
+            displacement[*a].0 -= dx / distance * force;
+            displacement[*a].1 -= dy / distance * force;
+            displacement[*b].0 += dx / distance * force;
+            displacement[*b].1 += dy / distance * force;
+        }
+
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacement) {
+            let length = dx.hypot(dy).max(0.01);
+            let step = length.min(temperature);
+            position.0 = (position.0 + dx / length * step).clamp(0.0, WIDTH);
+            position.1 = (position.1 + dy / length * step).clamp(0.0, HEIGHT);
+        }
+    }
+
+    // Scale into the visible area
+    let (min_x, max_x) = min_max(positions.iter().map(|o| o.0));
+    let (min_y, max_y) = min_max(positions.iter().map(|o| o.1));
+    let scale = |value: f64, min: f64, max: f64, size: f64| {
+        if (max - min).abs() < f64::EPSILON {
+            size / 2.0
+        } else {
+            MARGIN + (value - min) / (max - min) * (size - 2.0 * MARGIN)
+        }
+    };
+    solarsystems
+        .into_iter()
+        .zip(positions)
+        .map(|(solarsystem, (x, y))| {
+            let x = scale(x, min_x, max_x, WIDTH);
+            let y = scale(y, min_y, max_y, HEIGHT);
+            (solarsystem, (x, y))
+        })
+        .collect()
+}
+
+fn delta(a: Point, b: Point) -> (f64, f64, f64) {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    (dx, dy, dx.hypot(dy).max(0.01))
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::MAX, f64::MIN), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+/// Red for 0 % security, green for 100 %
+fn security_color(security: u8) -> String {
+    let hue = u16::from(security.min(100)) * 12 / 10;
+    format!("hsl({}, 70%, 45%)", hue)
+}
+
+/// Renders the solarsystems as SVG map.
+///
+/// Solarsystems are colored by their security and show their amount of stations and stargates.
+/// Stargates only existing in one direction are drawn with an arrow.
+#[must_use]
+pub fn render_svg(solarsystems: &Solarsystems) -> String {
+    let mut svg = String::new();
+    // Writing into a String never fails
+    write_svg(&mut svg, solarsystems).unwrap_or_default();
+    svg
+}
+
+fn write_svg(svg: &mut String, solarsystems: &Solarsystems) -> std::fmt::Result {
+    let universe = Universe::from(solarsystems);
+    let positions = layout(&universe);
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+        w = WIDTH,
+        h = HEIGHT
+    )?;
+    svg.push_str(r##"<defs><marker id="arrow" viewBox="0 0 10 10" refX="28" refY="5" markerWidth="6" markerHeight="6" orient="auto"><path d="M 0 0 L 10 5 L 0 10 z" fill="#888"/></marker></defs>"##);
+    svg.push('\n');
+    writeln!(
+        svg,
+        r##"<rect width="{}" height="{}" fill="#111"/>"##,
+        WIDTH, HEIGHT
+    )?;
+
+    for (solarsystem, (x1, y1)) in &positions {
+        for target in universe.neighbors(*solarsystem) {
+            let both_directions = universe.neighbors(target).contains(solarsystem);
+            if both_directions && target < *solarsystem {
+                // Already drawn from the other side
+                continue;
+            }
+            if let Some((x2, y2)) = positions.get(&target) {
+                let marker = if both_directions {
+                    ""
+                } else {
+                    r#" marker-end="url(#arrow)""#
+                };
+                writeln!(
+                    svg,
+                    r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#888" stroke-width="2"{}/>"##,
+                    x1, y1, x2, y2, marker
+                )?;
+            }
+        }
+    }
+
+    for (solarsystem, (x, y)) in &positions {
+        let details = solarsystems.get(solarsystem);
+        writeln!(
+            svg,
+            r##"<g><circle cx="{x:.1}" cy="{y:.1}" r="18" fill="{color}" stroke="#eee"/><text x="{x:.1}" y="{name_y:.1}" fill="#eee" font-size="14" text-anchor="middle">{name} ({security})</text><text x="{x:.1}" y="{info_y:.1}" fill="#aaa" font-size="11" text-anchor="middle">{stations} stations, {stargates} stargates</text></g>"##,
+            x = x,
+            y = y,
+            color = security_color(details.security),
+            name = solarsystem,
+            security = details.security,
+            name_y = y - 26.0,
+            info_y = y + 34.0,
+            stations = details.stations.len(),
+            stargates = details.stargates.len(),
+        )?;
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(())
+}
+
+#[test]
+fn layout_is_within_bounds_and_distinct() {
+    let universe = super::example();
+    let positions = layout(&universe);
+    assert_eq!(positions.len(), 6);
+    let points = positions.values().collect::<Vec<_>>();
+    for (i, (x, y)) in points.iter().enumerate() {
+        assert!((0.0..=WIDTH).contains(x), "x {}", x);
+        assert!((0.0..=HEIGHT).contains(y), "y {}", y);
+        for (other_x, other_y) in points.iter().skip(i + 1) {
+            assert!((x - other_x).hypot(y - other_y) > 20.0, "overlapping");
+        }
+    }
+}
+
+#[test]
+fn layout_is_deterministic() {
+    let universe = super::example();
+    assert_eq!(layout(&universe), layout(&universe));
+}
+
+#[test]
+fn svg_contains_every_solarsystem() {
+    let statics = crate::fixed::Statics::default();
+    let svg = render_svg(&statics.solarsystems);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+    for (solarsystem, details) in &statics.solarsystems.data {
+        assert!(svg.contains(&format!(">{} ({})<", solarsystem, details.security)));
+    }
+    assert!(svg.contains(">4 stations, 2 stargates<"));
+    // All stargates are symmetric
+    assert!(!svg.contains("marker-end"));
+}
+
+#[test]
+fn svg_marks_one_directional_stargates() {
+    let mut statics = crate::fixed::Statics::default();
+    statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Vosu)
+        .unwrap()
+        .stargates
+        .clear();
+    let svg = render_svg(&statics.solarsystems);
+    assert_eq!(svg.matches("marker-end").count(), 1);
+}
@@ -0,0 +1,210 @@
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::fixed::solarsystem::Solarsystem;
+use crate::fixed::Solarsystems;
+
+mod analysis;
+pub mod generate;
+pub mod map;
+mod route;
+mod state;
+mod wormhole;
+
+pub use analysis::Analysis;
+pub use route::{Preference as RoutePreference, Route, RouteHop, HIGH_SECURITY};
+pub use state::{SecurityDrift, SolarsystemState};
+pub use wormhole::{Wormhole, WormholeEnd};
+
+/// The solarsystems as a graph connected via stargates.
+///
+/// Connections are directed: a stargate from A to B does not mean there is a stargate from B to A.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Universe {
+    connections: BTreeMap<Solarsystem, BTreeSet<Solarsystem>>,
+    security: BTreeMap<Solarsystem, u8>,
+}
+
+impl From<&Solarsystems> for Universe {
+    fn from(solarsystems: &Solarsystems) -> Self {
+        let connections = solarsystems
+            .data
+            .iter()
+            .map(|(solarsystem, details)| {
+                (*solarsystem, details.stargates.keys().copied().collect())
+            })
+            .collect();
+        let security = solarsystems
+            .data
+            .iter()
+            .map(|(solarsystem, details)| (*solarsystem, details.security))
+            .collect();
+        Self {
+            connections,
+            security,
+        }
+    }
+}
+
+impl Universe {
+    /// All known solarsystems in order
+    pub fn solarsystems(&self) -> impl Iterator<Item = Solarsystem> + '_ {
+        self.connections.keys().copied()
+    }
+
+    #[must_use]
+    pub fn security(&self, solarsystem: Solarsystem) -> Option<u8> {
+        self.security.get(&solarsystem).copied()
+    }
+
+    /// Solarsystems reachable with a single jump
+    #[must_use]
+    pub fn neighbors(&self, solarsystem: Solarsystem) -> Vec<Solarsystem> {
+        self.connections
+            .get(&solarsystem)
+            .map(|o| o.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Shortest route including the origin and the target.
+    /// Returns None when the target is not reachable.
+    #[must_use]
+    pub fn route(&self, from: Solarsystem, to: Solarsystem) -> Option<Vec<Solarsystem>> {
+        let mut previous = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        previous.insert(from, from);
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut route = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    route.push(current);
+                }
+                route.reverse();
+                return Some(route);
+            }
+            for neighbor in self.neighbors(current) {
+                if let Entry::Vacant(entry) = previous.entry(neighbor) {
+                    entry.insert(current);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    /// Amount of jumps needed to get from one solarsystem to the other.
+    /// Returns None when the target is not reachable.
+    #[must_use]
+    pub fn jump_distance(&self, from: Solarsystem, to: Solarsystem) -> Option<usize> {
+        self.route(from, to).map(|route| route.len() - 1)
+    }
+
+    /// All solarsystems that can be reached with at most `jumps` jumps including the origin itself.
+    /// Value: The amount of jumps needed
+    #[must_use]
+    pub fn within_jumps(&self, from: Solarsystem, jumps: usize) -> BTreeMap<Solarsystem, usize> {
+        let mut result = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        result.insert(from, 0);
+        queue.push_back((from, 0));
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance >= jumps {
+                continue;
+            }
+            for neighbor in self.neighbors(current) {
+                if let Entry::Vacant(entry) = result.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+fn example() -> Universe {
+    Universe::from(&crate::fixed::Statics::default().solarsystems)
+}
+
+#[test]
+fn neighbors_are_the_stargates() {
+    let universe = example();
+    assert_eq!(
+        universe.neighbors(Solarsystem::Wabinihwa),
+        vec![Solarsystem::Arama, Solarsystem::Liagi]
+    );
+    assert_eq!(
+        universe.neighbors(Solarsystem::Vosu),
+        vec![Solarsystem::Plagar]
+    );
+}
+
+#[test]
+fn route_to_itself() {
+    let universe = example();
+    assert_eq!(
+        universe.route(Solarsystem::Vosu, Solarsystem::Vosu),
+        Some(vec![Solarsystem::Vosu])
+    );
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Vosu, Solarsystem::Vosu),
+        Some(0)
+    );
+}
+
+#[test]
+fn shortest_route_from_home_to_vosu() {
+    let universe = example();
+    assert_eq!(
+        universe.route(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        Some(vec![
+            Solarsystem::Wabinihwa,
+            Solarsystem::Arama,
+            Solarsystem::Plagar,
+            Solarsystem::Vosu,
+        ])
+    );
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        Some(3)
+    );
+}
+
+#[test]
+fn unreachable_has_no_route() {
+    let mut statics = crate::fixed::Statics::default();
+    statics
+        .solarsystems
+        .data
+        .get_mut(&Solarsystem::Plagar)
+        .unwrap()
+        .stargates
+        .remove(&Solarsystem::Vosu);
+    let universe = Universe::from(&statics.solarsystems);
+    assert_eq!(
+        universe.route(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        None
+    );
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        None
+    );
+}
+
+#[test]
+fn within_jumps_works() {
+    let universe = example();
+    let mut expected = BTreeMap::new();
+    expected.insert(Solarsystem::Wabinihwa, 0);
+    expected.insert(Solarsystem::Arama, 1);
+    expected.insert(Solarsystem::Liagi, 1);
+    assert_eq!(universe.within_jumps(Solarsystem::Wabinihwa, 1), expected);
+
+    expected.insert(Solarsystem::Iramil, 2);
+    expected.insert(Solarsystem::Plagar, 2);
+    assert_eq!(universe.within_jumps(Solarsystem::Wabinihwa, 2), expected);
+}
@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fixed::solarsystem::Solarsystem;
+
+use super::Universe;
+
+/// Solarsystems with at least this security are considered high security.
+pub const HIGH_SECURITY: u8 = 50;
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", rename = "RoutePreference")]
+pub enum Preference {
+    /// Least amount of jumps
+    #[default]
+    Shortest,
+    /// Takes more jumps in order to fly through solarsystems with higher security
+    PreferHighSecurity,
+    /// Never flies through solarsystems below `HIGH_SECURITY`.
+    /// Origin and target are allowed to be low security.
+    AvoidLowSecurity,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RouteHop {
+    pub solarsystem: Solarsystem,
+    pub security: u8,
+}
+
+/// Every solarsystem of the route including origin and target.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Route(pub Vec<RouteHop>);
+
+impl Route {
+    #[must_use]
+    pub fn jumps(&self) -> usize {
+        self.0.len().saturating_sub(1)
+    }
+
+    /// The lowest security on the way
+    #[must_use]
+    pub fn min_security(&self) -> Option<u8> {
+        self.0.iter().map(|o| o.security).min()
+    }
+}
+
+impl Universe {
+    /// Plans a route with the given preference.
+    ///
+    /// Solarsystems in `avoid` are never flown through.
+    /// Like with `AvoidLowSecurity` origin and target are always allowed.
+    /// Returns None when there is no route meeting the requirements.
+    #[must_use]
+    pub fn plan_route(
+        &self,
+        from: Solarsystem,
+        to: Solarsystem,
+        preference: Preference,
+        avoid: &[Solarsystem],
+    ) -> Option<Route> {
+        let cost = |solarsystem: Solarsystem| -> Option<u32> {
+            let security = self.security(solarsystem)?;
+            if solarsystem == to {
+                return Some(1);
+            }
+            if avoid.contains(&solarsystem) {
+                return None;
+            }
+            match preference {
+                Preference::Shortest => Some(1),
+                Preference::PreferHighSecurity => Some(1 + u32::from(100 - security.min(100))),
+                Preference::AvoidLowSecurity => (security >= HIGH_SECURITY).then_some(1),
+            }
+        };
+
+        let route = self.cheapest_route(from, to, cost)?;
+        let hops = route
+            .into_iter()
+            .map(|solarsystem| RouteHop {
+                solarsystem,
+                security: self.security(solarsystem).unwrap_or_default(),
+            })
+            .collect();
+        Some(Route(hops))
+    }
+
+    /// Dijkstra with the cost of entering a solarsystem.
+    /// None as cost means the solarsystem can not be entered.
+    fn cheapest_route<F>(
+        &self,
+        from: Solarsystem,
+        to: Solarsystem,
+        cost: F,
+    ) -> Option<Vec<Solarsystem>>
+    where
+        F: Fn(Solarsystem) -> Option<u32>,
+    {
+        let mut best = BTreeMap::new();
+        let mut previous = BTreeMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(from, 0_u32);
+        queue.push(Reverse((0_u32, from)));
+
+        while let Some(Reverse((total, current))) = queue.pop() {
+            if current == to {
+                let mut route = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    route.push(current);
+                }
+                route.reverse();
+                return Some(route);
+            }
+            if best.get(&current).is_some_and(|o| *o < total) {
+                continue;
+            }
+            for neighbor in self.neighbors(current) {
+                if let Some(cost) = cost(neighbor) {
+                    let total = total.saturating_add(cost);
+                    if best.get(&neighbor).is_none_or(|o| total < *o) {
+                        best.insert(neighbor, total);
+                        previous.insert(neighbor, current);
+                        queue.push(Reverse((total, neighbor)));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+fn solarsystems(route: &Route) -> Vec<Solarsystem> {
+    route.0.iter().map(|o| o.solarsystem).collect()
+}
+
+#[test]
+fn shortest_equals_universe_route() {
+    let universe = super::example();
+    let route = universe
+        .plan_route(
+            Solarsystem::Wabinihwa,
+            Solarsystem::Vosu,
+            Preference::Shortest,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        Some(solarsystems(&route)),
+        universe.route(Solarsystem::Wabinihwa, Solarsystem::Vosu)
+    );
+    assert_eq!(route.jumps(), 3);
+    assert_eq!(
+        route.0[1],
+        RouteHop {
+            solarsystem: Solarsystem::Arama,
+            security: 51,
+        }
+    );
+}
+
+#[test]
+fn prefer_high_security_takes_detour() {
+    let universe = super::example();
+    let shortest = universe
+        .plan_route(
+            Solarsystem::Wabinihwa,
+            Solarsystem::Iramil,
+            Preference::Shortest,
+            &[],
+        )
+        .unwrap();
+    let safest = universe
+        .plan_route(
+            Solarsystem::Wabinihwa,
+            Solarsystem::Iramil,
+            Preference::PreferHighSecurity,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(shortest.jumps(), 2);
+    assert_eq!(
+        solarsystems(&safest),
+        vec![
+            Solarsystem::Wabinihwa,
+            Solarsystem::Liagi,
+            Solarsystem::Iramil,
+        ]
+    );
+    assert!(safest.min_security() >= shortest.min_security());
+}
+
+#[test]
+fn avoid_low_security_allows_target() {
+    let universe = super::example();
+    let route = universe
+        .plan_route(
+            Solarsystem::Arama,
+            Solarsystem::Plagar,
+            Preference::AvoidLowSecurity,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        solarsystems(&route),
+        vec![Solarsystem::Arama, Solarsystem::Plagar]
+    );
+}
+
+#[test]
+fn avoid_low_security_finds_nothing() {
+    let universe = super::example();
+    let route = universe.plan_route(
+        Solarsystem::Wabinihwa,
+        Solarsystem::Vosu,
+        Preference::AvoidLowSecurity,
+        &[],
+    );
+    assert_eq!(route, None);
+}
+
+#[test]
+fn avoid_list_is_respected() {
+    let universe = super::example();
+    let route = universe
+        .plan_route(
+            Solarsystem::Wabinihwa,
+            Solarsystem::Iramil,
+            Preference::Shortest,
+            &[Solarsystem::Arama],
+        )
+        .unwrap();
+    assert_eq!(
+        solarsystems(&route),
+        vec![
+            Solarsystem::Wabinihwa,
+            Solarsystem::Liagi,
+            Solarsystem::Iramil,
+        ]
+    );
+
+    let route = universe.plan_route(
+        Solarsystem::Wabinihwa,
+        Solarsystem::Vosu,
+        Preference::Shortest,
+        &[Solarsystem::Plagar],
+    );
+    assert_eq!(route, None);
+}
+
+#[test]
+fn can_serde_parse_route() {
+    let data = Route(vec![
+        RouteHop {
+            solarsystem: Solarsystem::Wabinihwa,
+            security: 92,
+        },
+        RouteHop {
+            solarsystem: Solarsystem::Arama,
+            security: 51,
+        },
+    ]);
+    crate::test_helper::can_serde_parse(&data);
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fixed::solarsystem::Solarsystem;
+use crate::market::Trade;
+use crate::site::{Actor, Log};
+
+use super::Universe;
+
+/// What happened in a solarsystem while the game is running.
+///
+/// The static `Details::security` is the baseline, `security_drift` is the change on top of it.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SolarsystemState {
+    /// Ships destroyed
+    pub kills: u32,
+    /// Jumps out of the solarsystem
+    pub jumps: u32,
+    pub mined_ore: u64,
+    pub trades: u32,
+    /// Total paperclips of all the trades
+    pub traded_paperclips: u64,
+    /// Percentage points added to the static security
+    #[serde(default)]
+    pub security_drift: i8,
+}
+
+/// How the security reacts to what is happening
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityDrift {
+    /// Percentage points lost per destroyed ship
+    pub per_kill: i8,
+    /// Percentage points recovered towards the baseline every round
+    pub recovery_per_round: i8,
+    /// The drift never gets further away from the baseline than this
+    pub max_drift: i8,
+}
+
+impl Default for SecurityDrift {
+    fn default() -> Self {
+        Self {
+            per_kill: 2,
+            recovery_per_round: 1,
+            max_drift: 30,
+        }
+    }
+}
+
+impl SolarsystemState {
+    /// Counts the happenings of a site within this solarsystem.
+    /// Pass the `log` of the `site::advance` output.
+    ///
+    /// When a drift is given each kill lowers the security.
+    pub fn record_log(&mut self, log: &[Log], drift: Option<SecurityDrift>) {
+        for entry in log {
+            match entry {
+                Log::RapidUnscheduledDisassembly(Actor::Npc(_) | Actor::Player(_)) => {
+                    self.kills = self.kills.saturating_add(1);
+                    if let Some(drift) = drift {
+                        self.change_drift(drift, drift.per_kill.saturating_neg());
+                    }
+                }
+                Log::Jump(_) => self.jumps = self.jumps.saturating_add(1),
+                Log::Mined((_, _, amount)) => {
+                    self.mined_ore = self.mined_ore.saturating_add(u64::from(*amount));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Counts a trade which happened at a station in this solarsystem (`trade.solarsystem`).
+    pub fn record_trade(&mut self, trade: &Trade) {
+        self.trades = self.trades.saturating_add(1);
+        self.traded_paperclips = self
+            .traded_paperclips
+            .saturating_add(u64::from(trade.amount).saturating_mul(trade.paperclips));
+    }
+
+    /// Moves the drift one round closer to the baseline
+    pub fn recover(&mut self, drift: SecurityDrift) {
+        let step = drift
+            .recovery_per_round
+            .unsigned_abs()
+            .min(self.security_drift.unsigned_abs());
+        let step = i8::try_from(step).unwrap_or(i8::MAX);
+        let change = if self.security_drift > 0 {
+            step.saturating_neg()
+        } else {
+            step
+        };
+        self.change_drift(drift, change);
+    }
+
+    fn change_drift(&mut self, drift: SecurityDrift, change: i8) {
+        let max = i8::try_from(drift.max_drift.unsigned_abs()).unwrap_or(i8::MAX);
+        self.security_drift = self
+            .security_drift
+            .saturating_add(change)
+            .clamp(max.saturating_neg(), max);
+    }
+
+    /// The security with the drift applied, still within 0 to 100
+    #[must_use]
+    pub fn effective_security(&self, baseline: u8) -> u8 {
+        let security = i16::from(baseline) + i16::from(self.security_drift);
+        u8::try_from(security.clamp(0, 100)).unwrap_or_default()
+    }
+}
+
+impl Universe {
+    /// Uses the effective security of the states for route planning.
+    pub fn apply_states(&mut self, states: &BTreeMap<Solarsystem, SolarsystemState>) {
+        for (solarsystem, security) in &mut self.security {
+            if let Some(state) = states.get(solarsystem) {
+                *security = state.effective_security(*security);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn player_actor() -> Actor {
+    Actor::Player((
+        crate::player::Player::Telegram(666),
+        crate::fixed::shiplayout::ShipLayout::default(),
+    ))
+}
+
+#[test]
+fn can_serde_parse() {
+    let data = SolarsystemState {
+        kills: 1,
+        jumps: 2,
+        mined_ore: 3,
+        trades: 4,
+        traded_paperclips: 5,
+        security_drift: -6,
+    };
+    crate::test_helper::can_serde_parse(&data);
+}
+
+#[test]
+fn records_log() {
+    use crate::fixed::item::Ore;
+    let mut state = SolarsystemState::default();
+    state.record_log(
+        &[
+            Log::Jump(player_actor()),
+            Log::Mined((player_actor(), Ore::Aromit, 5)),
+            Log::Mined((player_actor(), Ore::Solmit, 3)),
+            Log::RapidUnscheduledDisassembly(player_actor()),
+            Log::RapidUnscheduledDisassembly(Actor::Asteroid(Ore::Aromit)),
+            Log::WarpIn(player_actor()),
+        ],
+        None,
+    );
+    assert_eq!(state.kills, 1);
+    assert_eq!(state.jumps, 1);
+    assert_eq!(state.mined_ore, 8);
+    assert_eq!(state.security_drift, 0);
+}
+
+#[test]
+fn records_trade() {
+    let mut state = SolarsystemState::default();
+    state.record_trade(&Trade::new_test(3, 42));
+    state.record_trade(&Trade::new_test(1, 10));
+    assert_eq!(state.trades, 2);
+    assert_eq!(state.traded_paperclips, 136);
+}
+
+#[test]
+fn kills_drift_security_and_recover() {
+    let drift = SecurityDrift {
+        per_kill: 10,
+        recovery_per_round: 4,
+        max_drift: 15,
+    };
+    let mut state = SolarsystemState::default();
+    let kill = Log::RapidUnscheduledDisassembly(player_actor());
+    state.record_log(&[kill, kill], Some(drift));
+    assert_eq!(state.security_drift, -15);
+    assert_eq!(state.effective_security(10), 0);
+    assert_eq!(state.effective_security(50), 35);
+
+    for expected in [-11, -7, -3, 0, 0] {
+        state.recover(drift);
+        assert_eq!(state.security_drift, expected);
+    }
+}
+
+#[test]
+fn extreme_drift_does_not_overflow() {
+    let drift = SecurityDrift {
+        per_kill: i8::MIN,
+        recovery_per_round: i8::MIN,
+        max_drift: i8::MIN,
+    };
+    let mut state = SolarsystemState {
+        security_drift: i8::MIN,
+        ..SolarsystemState::default()
+    };
+    state.recover(drift);
+    assert_eq!(state.security_drift, -1);
+    state.record_log(
+        &[Log::RapidUnscheduledDisassembly(player_actor())],
+        Some(drift),
+    );
+    assert_eq!(state.security_drift, 126);
+    state.recover(drift);
+    assert_eq!(state.security_drift, 0);
+}
+
+#[test]
+fn route_planning_uses_effective_security() {
+    let mut universe = super::example();
+    let mut states = BTreeMap::new();
+    states.insert(
+        Solarsystem::Liagi,
+        SolarsystemState {
+            security_drift: -80,
+            ..SolarsystemState::default()
+        },
+    );
+    universe.apply_states(&states);
+    assert_eq!(universe.security(Solarsystem::Liagi), Some(20));
+    assert_eq!(universe.security(Solarsystem::Wabinihwa), Some(92));
+}
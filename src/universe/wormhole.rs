@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use crate::fixed::solarsystem::Solarsystem;
+use crate::site::Site;
+
+use super::Universe;
+
+/// One side of a wormhole
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WormholeEnd {
+    pub solarsystem: Solarsystem,
+    /// The planet the wormhole site is near to
+    pub planet: u8,
+}
+
+/// Temporary connection between two solarsystems in both directions.
+///
+/// Collapses when it runs out of rounds or jumps, whatever comes first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS), ts(export))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Wormhole {
+    pub ends: [WormholeEnd; 2],
+    pub remaining_rounds: u32,
+    pub remaining_jumps: u32,
+}
+
+impl Wormhole {
+    #[must_use]
+    pub const fn new(a: WormholeEnd, b: WormholeEnd, rounds: u32, jumps: u32) -> Self {
+        Self {
+            ends: [a, b],
+            remaining_rounds: rounds,
+            remaining_jumps: jumps,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_collapsed(&self) -> bool {
+        self.remaining_rounds == 0 || self.remaining_jumps == 0
+    }
+
+    /// The site of the wormhole within the given solarsystem and the planet it is near to.
+    /// None when the wormhole does not end in the solarsystem.
+    #[must_use]
+    pub fn site_in(&self, solarsystem: Solarsystem) -> Option<(u8, Site)> {
+        let [a, b] = self.ends;
+        if a.solarsystem == solarsystem {
+            Some((a.planet, Site::Wormhole(b.solarsystem)))
+        } else if b.solarsystem == solarsystem {
+            Some((b.planet, Site::Wormhole(a.solarsystem)))
+        } else {
+            None
+        }
+    }
+
+    /// Checks if this wormhole connects the two solarsystems in any direction
+    #[must_use]
+    pub fn connects(&self, a: Solarsystem, b: Solarsystem) -> bool {
+        let [first, second] = self.ends;
+        (first.solarsystem == a && second.solarsystem == b)
+            || (first.solarsystem == b && second.solarsystem == a)
+    }
+
+    /// Something jumped through the wormhole.
+    /// Call this with the `Log::Jump` entries of `site::advance` for `Site::Wormhole`.
+    /// # Returns
+    /// Returns true when the wormhole collapsed
+    pub fn jumped(&mut self) -> bool {
+        self.remaining_jumps = self.remaining_jumps.saturating_sub(1);
+        self.is_collapsed()
+    }
+
+    /// Progresses the wormhole by one round.
+    /// # Returns
+    /// Returns true when the wormhole collapsed
+    pub fn advance(&mut self) -> bool {
+        self.remaining_rounds = self.remaining_rounds.saturating_sub(1);
+        self.is_collapsed()
+    }
+}
+
+impl Universe {
+    /// Adds the connections of the wormholes which did not collapse yet.
+    /// Routes will then use them like stargates.
+    pub fn add_wormholes(&mut self, wormholes: &[Wormhole]) {
+        for wormhole in wormholes.iter().filter(|o| !o.is_collapsed()) {
+            let [a, b] = wormhole.ends;
+            self.connections
+                .entry(a.solarsystem)
+                .or_default()
+                .insert(b.solarsystem);
+            self.connections
+                .entry(b.solarsystem)
+                .or_default()
+                .insert(a.solarsystem);
+        }
+    }
+}
+
+#[cfg(test)]
+fn example() -> Wormhole {
+    Wormhole::new(
+        WormholeEnd {
+            solarsystem: Solarsystem::Wabinihwa,
+            planet: 1,
+        },
+        WormholeEnd {
+            solarsystem: Solarsystem::Vosu,
+            planet: 2,
+        },
+        3,
+        2,
+    )
+}
+
+#[test]
+fn can_serde_parse() {
+    crate::test_helper::can_serde_parse(&example());
+}
+
+#[test]
+fn site_in_both_ends() {
+    let wormhole = example();
+    assert_eq!(
+        wormhole.site_in(Solarsystem::Wabinihwa),
+        Some((1, Site::Wormhole(Solarsystem::Vosu)))
+    );
+    assert_eq!(
+        wormhole.site_in(Solarsystem::Vosu),
+        Some((2, Site::Wormhole(Solarsystem::Wabinihwa)))
+    );
+    assert_eq!(wormhole.site_in(Solarsystem::Arama), None);
+    assert!(wormhole.connects(Solarsystem::Vosu, Solarsystem::Wabinihwa));
+}
+
+#[test]
+fn collapses_after_rounds() {
+    let mut wormhole = example();
+    assert!(!wormhole.advance());
+    assert!(!wormhole.advance());
+    assert!(wormhole.advance());
+}
+
+#[test]
+fn collapses_after_jumps() {
+    let mut wormhole = example();
+    assert!(!wormhole.jumped());
+    assert!(wormhole.jumped());
+}
+
+#[test]
+fn route_uses_wormhole() {
+    let mut universe = super::example();
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        Some(3)
+    );
+
+    let mut collapsed = example();
+    collapsed.remaining_jumps = 0;
+    universe.add_wormholes(&[collapsed]);
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        Some(3)
+    );
+
+    universe.add_wormholes(&[example()]);
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Wabinihwa, Solarsystem::Vosu),
+        Some(1)
+    );
+    assert_eq!(
+        universe.jump_distance(Solarsystem::Vosu, Solarsystem::Wabinihwa),
+        Some(1)
+    );
+}